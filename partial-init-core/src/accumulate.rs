@@ -0,0 +1,130 @@
+//! Support for adding items to collection fields one at a time
+//!
+//! The FIFs created by `#[each = name]` add a single item to a collection field,
+//! the first item turns the state of the field into an `Accumulated`, which holds the
+//! old state alongside the items, and `build` adds the items to the value of the old state.
+
+use super::*;
+
+/// The state of a field that items were added to by a FIF created by `#[each = name]`
+///
+/// If the state before the first item was initialized, then the items are added to
+/// it's value when the value is built, other-wise the items are the value of the field.
+/// Either way the field is initialized.
+pub struct Accumulated<T, S> {
+    base: S,
+    items: T,
+}
+
+impl<T, S> Accumulated<T, S> {
+    #[inline]
+    pub(crate) fn new<I>(base: S, item: I) -> Self
+    where
+        T: Default + Extend<I>,
+    {
+        let mut items = T::default();
+        items.extend(Some(item));
+        Accumulated { base, items }
+    }
+}
+
+// `T` is a parameter of `Accumulated` so that these don't overlap, see `FieldState`
+impl<F: FieldName, T, S: FieldState<F, T>> FieldState<F, T> for Accumulated<T, S> {}
+
+impl<F: FieldName, T, S> Init<F, T> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    #[inline]
+    fn get(self) -> T {
+        match self.base.get() {
+            Some(mut value) => {
+                value.extend(self.items);
+                value
+            }
+            None => self.items,
+        }
+    }
+}
+
+impl<F: FieldName, T, S> MaybeInit<F, T> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    #[inline(always)]
+    fn get(self) -> Option<T> {
+        Some(Init::<F, _>::get(self))
+    }
+}
+
+impl<F: FieldName, T, S, E> TryInit<F, T, E> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: TryInit<F, T, E>,
+{
+    #[inline]
+    fn try_get(self) -> Result<Option<T>, E> {
+        Ok(Some(match self.base.try_get()? {
+            Some(mut value) => {
+                value.extend(self.items);
+                value
+            }
+            None => self.items,
+        }))
+    }
+}
+
+impl<F: FieldName, T, S> AsyncInit<F, T> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    type Future = future::Ready<T>;
+
+    #[inline(always)]
+    fn get(self) -> future::Ready<T> {
+        future::ready(Init::<F, _>::get(self))
+    }
+}
+
+impl<F: FieldName, T, S> AsyncMaybeInit<F, T> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    type Future = future::Ready<Option<T>>;
+
+    #[inline(always)]
+    fn get(self) -> future::Ready<Option<T>> {
+        future::ready(Some(Init::<F, _>::get(self)))
+    }
+}
+
+impl<F: FieldName, T, S, R: MaybeInit<F, T>> Merge<F, T, R> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn merge(self, _: R) -> Self {
+        self
+    }
+}
+
+impl<F: FieldName, T, S, I> Accumulate<F, T, I> for Accumulated<T, S>
+where
+    T: IntoIterator + Extend<<T as IntoIterator>::Item> + Extend<I>,
+    S: MaybeInit<F, T>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn push(mut self, item: I) -> Self {
+        self.items.extend(Some(item));
+        self
+    }
+}
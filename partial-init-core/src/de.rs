@@ -0,0 +1,104 @@
+//! This module is used by partial-init-derive to deserialize `Partial*` types,
+//! when the `serde` feature is enabled
+//!
+//! Each `Partial*` type is deserialized from a map, where the keys are
+//! the names of the fields (see `FieldName::NAME`).
+
+use super::*;
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor};
+
+/// This is implemented by partial-init-derive for every state of a `Partial*` type
+/// that can be deserialized
+pub trait DeserializePartial<'de>: Sized {
+    /// The names of all of the fields that can be deserialized,
+    /// fields that are hidden by their visibility aren't included
+    const FIELDS: &'static [&'static str];
+
+    /// Reads the fields from a map, and checks that they match this state
+    fn visit_map<A: MapAccess<'de>>(map: A) -> Result<Self, A::Error>;
+}
+
+/// Deserializes a `Partial*` type as a struct with the given name
+pub fn deserialize<'de, P, D>(name: &'static str, deserializer: D) -> Result<P, D::Error>
+where
+    P: DeserializePartial<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_struct(name, P::FIELDS, PartialVisitor(PhantomData))
+}
+
+struct PartialVisitor<P>(PhantomData<P>);
+
+impl<'de, P: DeserializePartial<'de>> Visitor<'de> for PartialVisitor<P> {
+    type Value = P;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a map of fields")
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<P, A::Error> {
+        P::visit_map(map)
+    }
+}
+
+/// Deserializes the name of a field into it's position in the list of fields,
+/// unknown fields are reported as errors
+#[derive(Clone, Copy)]
+pub struct FieldSeed(pub &'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = usize;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldSeed {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a field name")
+    }
+
+    fn visit_u64<E: de::Error>(self, index: u64) -> Result<usize, E> {
+        if index < self.0.len() as u64 {
+            Ok(index as usize)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Unsigned(index), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<usize, E> {
+        self.0
+            .iter()
+            .position(|&field| field == name)
+            .ok_or_else(|| E::unknown_field(name, self.0))
+    }
+
+    fn visit_bytes<E: de::Error>(self, name: &[u8]) -> Result<usize, E> {
+        match std::str::from_utf8(name) {
+            Ok(name) => self.visit_str(name),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(name), &self)),
+        }
+    }
+}
+
+/// Converts the value read for a field into the state of the field,
+/// reporting a missing field or a field that must not be set as errors
+#[inline]
+pub fn from_option<F, T, S, E>(value: Option<T>) -> Result<S, E>
+where
+    F: FieldName,
+    S: FromOption<F, T>,
+    E: de::Error,
+{
+    match S::from_option(value) {
+        Ok(state) => Ok(state),
+        Err(None) => Err(E::missing_field(F::NAME)),
+        Err(Some(_)) => Err(E::custom(format_args!("field `{}` must not be set", F::NAME))),
+    }
+}
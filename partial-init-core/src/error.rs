@@ -0,0 +1,352 @@
+use super::*;
+
+/// The error returned by `try_build` on types created by partial-init-derive
+/// when some of the fields that are not marked `#[default]` were not initialized
+///
+/// The names of the fields are the names given by `FieldName::NAME`,
+/// which are the names of the field-initializing-functions
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingFields {
+    required: &'static [&'static str],
+    missing: u128,
+}
+
+impl MissingFields {
+    /// The maximum number of required fields that can be tracked
+    pub const MAX_FIELDS: usize = 128;
+
+    /// Creates a `MissingFields` with no missing fields
+    ///
+    /// `required` is the list of names of all of the fields that could be missing
+    #[inline]
+    pub fn new(required: &'static [&'static str]) -> Self {
+        MissingFields { required, missing: 0 }
+    }
+
+    /// Marks the `index`th field in `required` as missing
+    ///
+    /// **Note** this panics if `index` is not less than `MissingFields::MAX_FIELDS`
+    #[inline]
+    pub fn insert(&mut self, index: usize) {
+        assert!(index < Self::MAX_FIELDS, "`MissingFields` can only track {} fields", Self::MAX_FIELDS);
+        self.missing |= 1 << index;
+    }
+
+    /// Checks if there are no missing fields
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.missing == 0
+    }
+
+    /// The number of missing fields
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.missing.count_ones() as usize
+    }
+
+    /// Checks if the field with the given name is missing
+    pub fn contains(&self, name: &str) -> bool {
+        self.iter().any(|field| field == name)
+    }
+
+    /// Iterates over the names of all of the missing fields
+    #[inline]
+    pub fn iter(&self) -> MissingFieldsIter {
+        MissingFieldsIter {
+            required: self.required.iter().enumerate(),
+            missing: self.missing,
+        }
+    }
+}
+
+/// This is never implemented, it is used by partial-init-derive on the `try_build` and
+/// `try_build_fallible` of types that have more fields without a default than
+/// `MissingFields::MAX_FIELDS`, so that calling them is an error that explains why
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has more fields without a default than `MissingFields` can track",
+    label = "`try_build` can't report this many missing fields",
+    note = "`MissingFields` can track at most 128 fields, give some of the fields a `#[default]`, or use `build` instead"
+)]
+pub trait TooManyRequiredFields {}
+
+impl MissingFields {
+    // `a`, `b`, `c`
+    fn fmt_names(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, field) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", field)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IntoIterator for &MissingFields {
+    type Item = &'static str;
+    type IntoIter = MissingFieldsIter;
+
+    #[inline]
+    fn into_iter(self) -> MissingFieldsIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for MissingFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl std::fmt::Display for MissingFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("missing fields: ")?;
+        self.fmt_names(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MissingFields {}
+
+/// An iterator over the names of the missing fields in a `MissingFields`
+#[derive(Clone)]
+pub struct MissingFieldsIter {
+    required: std::iter::Enumerate<std::slice::Iter<'static, &'static str>>,
+    missing: u128,
+}
+
+impl Iterator for MissingFieldsIter {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<&'static str> {
+        let missing = self.missing;
+
+        self.required
+            .by_ref()
+            .find(|&(i, _)| missing & (1 << i) != 0)
+            .map(|(_, &field)| field)
+    }
+}
+
+/// The error returned by `into_partial` on the `Dyn*Builder` types created by
+/// partial-init-derive when some of the fields don't match the requested state
+pub struct IntoPartialError<D> {
+    /// The builder that was being converted, unchanged
+    pub builder: D,
+    /// The fields that were not set, but needed to be initialized,
+    /// or were set, but needed to be uninitialized
+    pub fields: MissingFields,
+}
+
+impl<D> std::fmt::Debug for IntoPartialError<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("IntoPartialError")
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+impl<D> std::fmt::Display for IntoPartialError<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("fields in the wrong state: ")?;
+        self.fields.fmt_names(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> std::error::Error for IntoPartialError<D> {}
+
+/// The error returned by `build` on types created by partial-init-derive
+/// when a validator rejects the value
+///
+/// Validators on fields (`#[validate(path)]`) return `Result<(), &'static str>`,
+/// and the error is tagged with the field that they validate. The validator on the
+/// type (`#[partial_init(validate = path)]`) returns `Result<(), ValidationError>`,
+/// so it can choose which field to blame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    /// The field that was rejected, or `None` if the value was rejected as a whole
+    pub field: Option<FieldInfo>,
+    /// Why the value was rejected
+    pub message: &'static str,
+}
+
+impl ValidationError {
+    /// Creates a `ValidationError` that rejects the value as a whole
+    #[inline]
+    pub fn new(message: &'static str) -> Self {
+        ValidationError { field: None, message }
+    }
+
+    /// Creates a `ValidationError` that rejects the field `F`
+    #[inline]
+    pub fn for_field<F: FieldName>(message: &'static str) -> Self {
+        ValidationError { field: Some(F::INFO), message }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "invalid value for field `{}`: {}", field.name, self.message),
+            None => write!(f, "invalid value: {}", self.message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// The error returned by `try_build` on types created by partial-init-derive
+/// that have validators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TryBuildError {
+    /// Some of the fields that are not marked `#[default]` were not initialized
+    MissingFields(MissingFields),
+    /// A validator rejected the value
+    Invalid(ValidationError),
+}
+
+impl From<MissingFields> for TryBuildError {
+    #[inline]
+    fn from(missing: MissingFields) -> Self {
+        TryBuildError::MissingFields(missing)
+    }
+}
+
+impl From<ValidationError> for TryBuildError {
+    #[inline]
+    fn from(err: ValidationError) -> Self {
+        TryBuildError::Invalid(err)
+    }
+}
+
+impl std::fmt::Display for TryBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TryBuildError::MissingFields(missing) => write!(f, "{}", missing),
+            TryBuildError::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryBuildError {}
+
+/// The error returned by `try_build_fallible` on types created by partial-init-derive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TryInitError<E> {
+    /// Some of the fields that are not marked `#[default]` were not initialized
+    MissingFields(MissingFields),
+    /// The source of a field failed, this is the first one that failed
+    Failed {
+        /// The field whose source failed
+        field: FieldInfo,
+        /// The error from the source
+        error: E,
+    },
+    /// A validator rejected the value
+    Invalid(ValidationError),
+}
+
+impl<E> From<MissingFields> for TryInitError<E> {
+    #[inline]
+    fn from(missing: MissingFields) -> Self {
+        TryInitError::MissingFields(missing)
+    }
+}
+
+impl<E> From<ValidationError> for TryInitError<E> {
+    #[inline]
+    fn from(err: ValidationError) -> Self {
+        TryInitError::Invalid(err)
+    }
+}
+
+impl<E> From<TryBuildError> for TryInitError<E> {
+    #[inline]
+    fn from(err: TryBuildError) -> Self {
+        match err {
+            TryBuildError::MissingFields(missing) => TryInitError::MissingFields(missing),
+            TryBuildError::Invalid(err) => TryInitError::Invalid(err),
+        }
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for TryInitError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TryInitError::MissingFields(missing) => write!(f, "{}", missing),
+            TryInitError::Failed { field, error } => write!(f, "failed to initialize field `{}`: {}", field.name, error),
+            TryInitError::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for TryInitError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TryInitError::Failed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The type of value that can be passed to `set_field` on the
+/// `Dyn*Builder` types created by partial-init-derive
+#[cfg(feature = "std")]
+pub type DynValue = Box<dyn std::any::Any>;
+
+/// The error returned by the `Dyn*Builder` types created by partial-init-derive
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Some of the fields that are not marked `#[default]` were not set
+    MissingFields(MissingFields),
+    /// There is no field with the given name
+    UnknownField(String),
+    /// The value given for the field does not have the type of the field
+    WrongType(FieldInfo),
+    /// A validator rejected the value
+    Invalid(ValidationError),
+}
+
+#[cfg(feature = "std")]
+impl From<MissingFields> for BuildError {
+    #[inline]
+    fn from(missing: MissingFields) -> Self {
+        BuildError::MissingFields(missing)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<TryBuildError> for BuildError {
+    #[inline]
+    fn from(err: TryBuildError) -> Self {
+        match err {
+            TryBuildError::MissingFields(missing) => BuildError::MissingFields(missing),
+            TryBuildError::Invalid(err) => BuildError::Invalid(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BuildError::MissingFields(missing) => write!(f, "{}", missing),
+            BuildError::UnknownField(name) => write!(f, "unknown field: `{}`", name),
+            BuildError::WrongType(field) => write!(
+                f,
+                "wrong type for field `{}`, expected `{}`",
+                field.name, field.type_name
+            ),
+            BuildError::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
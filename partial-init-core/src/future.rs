@@ -0,0 +1,108 @@
+//! Support for initializing fields with futures
+//!
+//! A field can be given a `Lazy` future with its field-initializing-function,
+//! then `build_async` on the types created by partial-init-derive awaits all
+//! of the futures at the same time, and builds the value. This doesn't depend
+//! on any executor, the future returned by `build_async` can be run by any of them.
+
+use super::*;
+
+use std::pin::Pin;
+
+pub use std::future::{poll_fn, ready, Future, Ready};
+pub use std::task::{Context, Poll};
+
+/// A field value that is produced by a future, it can only be built with `build_async`
+///
+/// The future must be `Unpin`, futures that aren't (like `async` blocks) can be
+/// pinned with `Box::pin`.
+pub struct Lazy<T, Fut>(Fut, PhantomData<fn() -> T>);
+
+impl<T, Fut: Future<Output = T> + Unpin> Lazy<T, Fut> {
+    /// Creates a field value that is produced by `fut`
+    #[inline]
+    pub fn future(fut: Fut) -> Self {
+        Lazy(fut, PhantomData)
+    }
+}
+
+// `T` is a parameter of `Lazy` so that these don't overlap, see `FieldState`
+impl<F: FieldName, T, Fut: Future<Output = T> + Unpin> FieldState<F, T> for Lazy<T, Fut> {}
+
+impl<F: FieldName, T, Fut: Future<Output = T> + Unpin> AsyncInit<F, T> for Lazy<T, Fut> {
+    type Future = Fut;
+
+    #[inline(always)]
+    fn get(self) -> Fut {
+        self.0
+    }
+}
+
+impl<F: FieldName, T, Fut: Future<Output = T> + Unpin> AsyncMaybeInit<F, T> for Lazy<T, Fut> {
+    type Future = Initialized<Fut>;
+
+    #[inline(always)]
+    fn get(self) -> Initialized<Fut> {
+        Initialized(self.0)
+    }
+}
+
+/// The future returned by `AsyncMaybeInit::get` on `Lazy`,
+/// it wraps the output of the future in `Some`
+pub struct Initialized<Fut>(Fut);
+
+impl<Fut: Future + Unpin> Future for Initialized<Fut> {
+    type Output = Option<Fut::Output>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.0).poll(cx) {
+            Poll::Ready(value) => Poll::Ready(Some(value)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// This is used by partial-init-derive to await the fields
+/// in `build_async` at the same time
+pub enum MaybeDone<Fut: Future> {
+    /// The future hasn't finished yet
+    Pending(Fut),
+    /// The future has finished, but the output wasn't taken yet
+    Done(Fut::Output),
+    /// The output was taken
+    Taken,
+}
+
+impl<Fut: Future + Unpin> MaybeDone<Fut> {
+    /// Wraps a future that hasn't been polled yet
+    #[inline]
+    pub fn new(fut: Fut) -> Self {
+        MaybeDone::Pending(fut)
+    }
+
+    /// Polls the future if it hasn't finished yet, and returns whether it has finished
+    pub fn poll(&mut self, cx: &mut Context) -> bool {
+        let value = match self {
+            MaybeDone::Pending(fut) => match Pin::new(fut).poll(cx) {
+                Poll::Ready(value) => value,
+                Poll::Pending => return false,
+            },
+            _ => return true,
+        };
+
+        *self = MaybeDone::Done(value);
+        true
+    }
+
+    /// Takes the output of the future, if it has finished and it wasn't taken yet
+    pub fn take(&mut self) -> Option<Fut::Output> {
+        match std::mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(value) => Some(value),
+            other => {
+                *self = other;
+                None
+            }
+        }
+    }
+}
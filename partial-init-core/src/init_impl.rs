@@ -19,9 +19,100 @@ macro_rules! impl_init {
                 Some(Init::<ImplInitF, _>::get(self))
             }
         }
+
+        impl<$($gen)*, ImplInitF: FieldName> FieldState<ImplInitF, $U> for $T {}
+
+        impl<$($gen)*, ImplInitF: FieldName, ImplInitE> TryInit<ImplInitF, $U, ImplInitE> for $T {
+            #[inline(always)]
+            fn try_get(self) -> Result<Option<$U>, ImplInitE> {
+                Ok(Some(Init::<ImplInitF, _>::get(self)))
+            }
+        }
+
+        impl<$($gen)*, ImplInitF: FieldName> AsyncInit<ImplInitF, $U> for $T {
+            type Future = future::Ready<$U>;
+
+            #[inline(always)]
+            fn get(self) -> future::Ready<$U> {
+                future::ready(Init::<ImplInitF, _>::get(self))
+            }
+        }
+
+        impl<$($gen)*, ImplInitF: FieldName> AsyncMaybeInit<ImplInitF, $U> for $T {
+            type Future = future::Ready<Option<$U>>;
+
+            #[inline(always)]
+            fn get(self) -> future::Ready<Option<$U>> {
+                future::ready(Some(Init::<ImplInitF, _>::get(self)))
+            }
+        }
+
+        impl<$($gen)*, ImplInitF: FieldName, ImplInitR: MaybeInit<ImplInitF, $U>> Merge<ImplInitF, $U, ImplInitR> for $T {
+            type Output = Self;
+
+            #[inline(always)]
+            fn merge(self, _: ImplInitR) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+// this is separate from `impl_init`, because the bounds would be trivially false
+// for some of the concrete field types of the conversions at the bottom of this file
+macro_rules! impl_accumulate {
+    ($(
+        impl($($gen:tt)*) Accumulate($U:ty) for $T:ty;
+    )*) => {$(
+        impl<$($gen)*, ImplInitF: FieldName, ImplInitI> Accumulate<ImplInitF, $U, ImplInitI> for $T
+        where
+            $U: Default + Extend<ImplInitI> + IntoIterator + Extend<<$U as IntoIterator>::Item>,
+        {
+            type Output = Accumulated<$U, Self>;
+
+            #[inline(always)]
+            fn push(self, item: ImplInitI) -> Accumulated<$U, Self> {
+                Accumulated::new(self, item)
+            }
+        }
     )*};
 }
 
+impl<U: FieldName, T> FieldState<U, T> for Uninit<U, T> {}
+impl<U: FieldName, T> FieldState<U, T> for Option<T> {}
+
+impl<U: FieldName, T, E> TryInit<U, T, E> for Uninit<U, T> {
+    #[inline(always)]
+    fn try_get(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+}
+
+impl<U: FieldName, T, E> TryInit<U, T, E> for Option<T> {
+    #[inline(always)]
+    fn try_get(self) -> Result<Option<T>, E> {
+        Ok(self)
+    }
+}
+
+impl<U: FieldName, T> AsyncMaybeInit<U, T> for Uninit<U, T> {
+    type Future = future::Ready<Option<T>>;
+
+    #[inline(always)]
+    fn get(self) -> future::Ready<Option<T>> {
+        future::ready(None)
+    }
+}
+
+impl<U: FieldName, T> AsyncMaybeInit<U, T> for Option<T> {
+    type Future = future::Ready<Option<T>>;
+
+    #[inline(always)]
+    fn get(self) -> future::Ready<Option<T>> {
+        future::ready(self)
+    }
+}
+
 impl<U: FieldName, T> MaybeInit<U, T> for Uninit<U, T> {
     #[inline(always)]
     fn get(self) -> Option<T> {
@@ -36,6 +127,75 @@ impl<U: FieldName, T> MaybeInit<U, T> for Option<T> {
     }
 }
 
+impl<U: FieldName, T, R: MaybeInit<U, T>> Merge<U, T, R> for Uninit<U, T> {
+    type Output = R;
+
+    #[inline(always)]
+    fn merge(self, other: R) -> R {
+        other
+    }
+}
+
+impl<U: FieldName, T, R: MaybeInit<U, T>> Merge<U, T, R> for Option<T> {
+    type Output = Option<T>;
+
+    #[inline(always)]
+    fn merge(self, other: R) -> Option<T> {
+        match self {
+            None => other.get(),
+            value => value,
+        }
+    }
+}
+
+impl<U: FieldName, T, I> Accumulate<U, T, I> for Uninit<U, T>
+where
+    T: Default + Extend<I> + IntoIterator + Extend<<T as IntoIterator>::Item>,
+{
+    type Output = Accumulated<T, Self>;
+
+    #[inline(always)]
+    fn push(self, item: I) -> Accumulated<T, Self> {
+        Accumulated::new(self, item)
+    }
+}
+
+impl<U: FieldName, T, I> Accumulate<U, T, I> for Option<T>
+where
+    T: Default + Extend<I> + IntoIterator + Extend<<T as IntoIterator>::Item>,
+{
+    type Output = Accumulated<T, Self>;
+
+    #[inline(always)]
+    fn push(self, item: I) -> Accumulated<T, Self> {
+        Accumulated::new(self, item)
+    }
+}
+
+impl<U: FieldName, T> FromOption<U, T> for Uninit<U, T> {
+    #[inline(always)]
+    fn from_option(value: Option<T>) -> Result<Self, Option<T>> {
+        match value {
+            None => Ok(Uninit::default()),
+            value => Err(value),
+        }
+    }
+}
+
+impl<U: FieldName, T> FromOption<U, T> for Option<T> {
+    #[inline(always)]
+    fn from_option(value: Option<T>) -> Result<Self, Option<T>> {
+        Ok(value)
+    }
+}
+
+impl<U: FieldName, T> FromOption<U, T> for T {
+    #[inline(always)]
+    fn from_option(value: Option<T>) -> Result<Self, Option<T>> {
+        value.ok_or(None)
+    }
+}
+
 impl_init! {
     impl(T) Init(T) for T {
         get(self) { self }
@@ -54,6 +214,13 @@ impl_init! {
     }
 }
 
+impl_accumulate! {
+    impl(T) Accumulate(T) for T;
+    impl(T) Accumulate((T,)) for T;
+    impl(T) Accumulate([T; 1]) for T;
+    impl(T) Accumulate(Option<T>) for T;
+}
+
 impl_init! {
     impl('a, T) Init(T) for &'a mut dyn (FnMut() -> T) {
         get(self) { self() }
@@ -68,6 +235,12 @@ impl_init! {
     }
 }
 
+impl_accumulate! {
+    impl('a, T) Accumulate(T) for &'a mut dyn FnMut() -> T;
+    impl('a, T) Accumulate(T) for &'a dyn Fn() -> T;
+    impl(T) Accumulate(T) for fn() -> T;
+}
+
 macro_rules! fn_impl {
     ($($bounds:ident)*) => {
         #[cfg(feature = "std")]
@@ -88,6 +261,14 @@ macro_rules! fn_impl {
                 get(self) { self() }
             }
         }
+
+        #[cfg(feature = "std")]
+        impl_accumulate! {
+            impl(T) Accumulate(T) for std::sync::Arc<dyn Fn() -> T $(+ $bounds)*>;
+            impl(T) Accumulate(T) for std::rc::Rc<dyn Fn() -> T $(+ $bounds)*>;
+            impl(T) Accumulate(T) for Box<dyn FnMut() -> T $(+ $bounds)*>;
+            impl(T) Accumulate(T) for Box<dyn Fn() -> T $(+ $bounds)*>;
+        }
     };
 }
 
@@ -96,46 +277,104 @@ fn_impl! { Send }
 fn_impl! { Sync }
 fn_impl! { Send Sync }
 
-/*
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl(T) Init(Box<T>) for T {
-            get(self) { Box::new(self) }
+macro_rules! impl_try_init {
+    ($(
+        impl($($gen:tt)*) TryInit($U:ty, $E:ty) for $T:ty {
+            try_get($($self:tt)*) { $($func:tt)* }
         }
-    }
+    )*) => {$(
+        impl<$($gen)*, ImplInitF: FieldName> FieldState<ImplInitF, $U> for $T {}
 
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl('a) Init(String) for &'a str {
-            get(self) { self.to_owned() }
+        impl<$($gen)*, ImplInitF: FieldName> TryInit<ImplInitF, $U, $E> for $T {
+            #[inline(always)]
+            fn try_get($($self)*) -> Result<Option<$U>, $E> {
+                ($($func)*).map(Some)
+            }
         }
+    )*};
+}
+
+impl_try_init! {
+    impl(T, E) TryInit(T, E) for Result<T, E> {
+        try_get(self) { self }
     }
 
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl('a) Init(std::ffi::CString) for &'a std::ffi::CStr {
-            get(self) { self.to_owned() }
-        }
+    impl('a, T, E) TryInit(T, E) for &'a mut dyn FnMut() -> Result<T, E> {
+        try_get(self) { self() }
     }
 
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl('a) Init(std::ffi::OsString) for &'a std::ffi::OsStr {
-            get(self) { self.to_owned() }
-        }
+    impl('a, T, E) TryInit(T, E) for &'a dyn Fn() -> Result<T, E> {
+        try_get(self) { self() }
     }
 
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl('a) Init(std::path::PathBuf) for &'a std::path::Path {
-            get(self) { self.to_owned() }
-        }
+    impl(T, E) TryInit(T, E) for fn() -> Result<T, E> {
+        try_get(self) { self() }
     }
+}
+
+macro_rules! try_fn_impl {
+    ($($bounds:ident)*) => {
+        #[cfg(feature = "std")]
+        impl_try_init! {
+            impl(T, E) TryInit(T, E) for std::sync::Arc<dyn Fn() -> Result<T, E> $(+ $bounds)*> {
+                try_get(self) { self() }
+            }
+
+            impl(T, E) TryInit(T, E) for std::rc::Rc<dyn Fn() -> Result<T, E> $(+ $bounds)*> {
+                try_get(self) { self() }
+            }
+
+            impl(T, E) TryInit(T, E) for Box<dyn FnMut() -> Result<T, E> $(+ $bounds)*> {
+                try_get(mut self) { self() }
+            }
 
-    #[cfg(feature = "std")]
-    impl_init! {
-        impl('a, T: Clone) Init(Vec<T>) for &'a [T] {
-            get(self) { self.to_vec() }
+            impl(T, E) TryInit(T, E) for Box<dyn Fn() -> Result<T, E> $(+ $bounds)*> {
+                try_get(self) { self() }
+            }
         }
+    };
+}
+
+try_fn_impl! {  }
+try_fn_impl! { Send }
+try_fn_impl! { Sync }
+try_fn_impl! { Send Sync }
+
+// Conversions from borrowed values to owned values, so that `.name(Convert("x"))` works for a
+// `String` field. These are on `Convert` instead of the borrowed values themselves, because
+// every extra `Init` impl for a field type can make inference fail where it used to succeed,
+// for example `Box<u32>` on a `Box<T>` field could be `T = u32` or `T = Box<u32>`
+#[cfg(feature = "std")]
+impl_init! {
+    impl(T) Init(Box<T>) for Convert<T> {
+        get(self) { Box::new(self.0) }
+    }
+
+    impl('a) Init(String) for Convert<&'a str> {
+        get(self) { self.0.to_owned() }
+    }
+
+    impl('a) Init(std::ffi::CString) for Convert<&'a std::ffi::CStr> {
+        get(self) { self.0.to_owned() }
+    }
+
+    impl('a) Init(std::ffi::OsString) for Convert<&'a std::ffi::OsStr> {
+        get(self) { self.0.to_owned() }
+    }
+
+    impl('a) Init(std::path::PathBuf) for Convert<&'a std::path::Path> {
+        get(self) { self.0.to_owned() }
+    }
+
+    impl('a, T: Clone) Init(Vec<T>) for Convert<&'a [T]> {
+        get(self) { self.0.to_vec() }
     }
-*/
\ No newline at end of file
+}
+
+// `String`, `CString`, `OsString` and `PathBuf` can't be iterated over, so items
+// can't be added to them with `#[each = name]`
+#[cfg(feature = "std")]
+impl_accumulate! {
+    impl(T) Accumulate(Box<T>) for Convert<T>;
+    impl('a, T: Clone) Accumulate(Vec<T>) for Convert<&'a [T]>;
+}
@@ -12,7 +12,26 @@
 #[cfg(not(feature = "std"))]
 extern crate core as std;
 
+#[cfg(feature = "serde")]
+pub extern crate serde;
+
 mod init_impl;
+mod error;
+mod accumulate;
+mod or;
+mod nested;
+pub mod future;
+
+#[cfg(feature = "serde")]
+pub mod de;
+
+pub use future::Lazy;
+pub use accumulate::Accumulated;
+pub use or::{Or, OrFuture};
+pub use nested::InitSome;
+pub use error::{IntoPartialError, MissingFields, MissingFieldsIter, TooManyRequiredFields, TryBuildError, TryInitError, ValidationError};
+#[cfg(feature = "std")]
+pub use error::{BuildError, DynValue};
 
 pub use std::marker::PhantomData;
 
@@ -35,9 +54,82 @@ pub trait PartialInit {
     fn uninit() -> Self::Uninitialized;
 }
 
+/// This is implemented by partial-init-derive for every state of a
+/// `Partial*` type that `build` can be called on, `T` is the output of `build`
+/// 
+/// This is used by the field-initializing-functions created by `#[nested]` and by `init!`,
+/// so that they work even if `build` was renamed with `#[partial_init(build_fn = ..)]`
+pub trait Build<T> {
+    /// Builds the value, this is the same as the `build` function on the `Partial*` type
+    fn build(self) -> T;
+}
+
 /// This is used by partial-init-derive to specify
-/// field names for better error messages
-pub trait FieldName { }
+/// field names for better error messages, and to
+/// describe the field at run-time
+pub trait FieldName {
+    /// The name of the field, as it was declared, without the `r#`
+    /// of a raw identifier, the fields of a tuple struct are `_{index}`
+    const NAME: &'static str;
+
+    /// The position of the field in the declaration of the type
+    const INDEX: usize;
+
+    /// The declared type of the field, as it was written
+    const TYPE_NAME: &'static str;
+
+    /// Whether the field has a default value,
+    /// i.e. that it *is* marked `#[default]`
+    const HAS_DEFAULT: bool;
+
+    /// All of the information about this field
+    const INFO: FieldInfo = FieldInfo {
+        name: Self::NAME,
+        index: Self::INDEX,
+        type_name: Self::TYPE_NAME,
+        has_default: Self::HAS_DEFAULT,
+    };
+}
+
+/// Run-time information about a field of a type that derives `PartialInit`
+///
+/// partial-init-derive creates a table of these for each type, named `FIELDS`,
+/// in the `__{name}__` module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The name of the field, see `FieldName::NAME`
+    pub name: &'static str,
+    /// The position of the field, see `FieldName::INDEX`
+    pub index: usize,
+    /// The declared type of the field, see `FieldName::TYPE_NAME`
+    pub type_name: &'static str,
+    /// Whether the field has a default value, see `FieldName::HAS_DEFAULT`
+    pub has_default: bool,
+}
+
+/// A borrowed value that is converted to an owned value when the value is built
+/// 
+/// This lets a field accept a value that isn't of its own type, `Convert(&str)`
+/// initializes a `String` field, `Convert(&[T])` a `Vec<T>` field, `Convert(&Path)`
+/// a `PathBuf` field, `Convert(&CStr)` a `CString` field, `Convert(&OsStr)` an
+/// `OsString` field, and `Convert(T)` a `Box<T>` field
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct Convert<T>(pub T);
+
+/// This is used by partial-init-derive to specify that a type
+/// is a state of a field, it is the bound on the generic parameters
+/// of the `Partial*` types and the field-initializing-functions
+/// 
+/// It is implemented by every type that implements `MaybeInit`, and by
+/// `future::Lazy`, which can only be read by `build_async`
+/// 
+/// Every `T` is an initialized state of a field of type `T`, so the
+/// states that wrap other values, like `future::Lazy`, take `T` as a
+/// parameter, other-wise their impls would overlap with the ones for `T`
+/// 
+/// This trait helps produce better error messages
+pub trait FieldState<F: FieldName, T> {}
 
 /// This is used by partial-init-derive to specify
 /// that a field must be initialized by the user
@@ -60,12 +152,102 @@ pub trait Init<F: FieldName, T>: MaybeInit<F, T> {
 /// 
 /// **Note** `get` will be called when `build` is called
 /// on types created by partial-init-derive
-pub trait MaybeInit<F: FieldName, T> {
+pub trait MaybeInit<F: FieldName, T>: FieldState<F, T> {
     /// Gets the value of the field if it
     /// is initialized, other-wise returns None
     fn get(self) -> Option<T>;
 }
 
+/// This is used by partial-init-derive to read the fields in `try_build_fallible`,
+/// where a field can be produced by a source that can fail, like `Result<T, E>`
+/// or `fn() -> Result<T, E>`
+/// 
+/// This is implemented by every type that implements `MaybeInit` in this crate,
+/// for any `E`, so that they can be used alongside the fallible sources.
+/// 
+/// **Note** `try_get` will be called when `try_build_fallible` is called
+/// on types created by partial-init-derive
+pub trait TryInit<F: FieldName, T, E>: FieldState<F, T> {
+    /// Gets the value of the field if it is initialized,
+    /// or the error if the source of the value failed
+    fn try_get(self) -> Result<Option<T>, E>;
+}
+
+/// This is used by partial-init-derive to specify that a field must be
+/// initialized by the user, in `build_async`
+/// 
+/// This is implemented by every type that implements `Init`, and
+/// by `future::Lazy`
+pub trait AsyncInit<F: FieldName, T>: FieldState<F, T> {
+    /// The future that produces the value of the field
+    type Future: future::Future<Output = T> + Unpin;
+
+    /// Gets the future that produces the value of the field
+    fn get(self) -> Self::Future;
+}
+
+/// This is used by partial-init-derive to specify that a field
+/// doesn't have to be initialized by the user, in `build_async`
+/// 
+/// This is implemented by every type that implements `MaybeInit`, and
+/// by `future::Lazy`
+pub trait AsyncMaybeInit<F: FieldName, T>: FieldState<F, T> {
+    /// The future that produces the value of the field, if it is initialized
+    type Future: future::Future<Output = Option<T>> + Unpin;
+
+    /// Gets the future that produces the value of the field
+    fn get(self) -> Self::Future;
+}
+
+/// This is used by partial-init-derive to merge two states of a field,
+/// the result is initialized if either of the states is initialized
+/// 
+/// If both states are initialized, then `self` wins and `other` is dropped
+/// without ever calling `get` on it. This is implemented for `Uninit`, `Option`
+/// and every type that implements `Init` in this crate, if you implement `Init`
+/// for your own type, then you should also implement `Merge` for it, with
+/// `Output = Self`, so that it can be used with `merge`.
+pub trait Merge<F: FieldName, T, R: MaybeInit<F, T>>: MaybeInit<F, T> {
+    /// The state of the field after merging
+    type Output: MaybeInit<F, T>;
+
+    /// Merges the two states, `self` wins if both are initialized
+    fn merge(self, other: R) -> Self::Output;
+}
+
+/// This is used by partial-init-derive to add an item to a collection field,
+/// in the FIFs created by `#[each = name]`
+/// 
+/// The first item turns the state into an `Accumulated`, later items are added
+/// to that `Accumulated`. This is implemented for `Uninit`, `Option`, `Accumulated`
+/// and every type that implements `Init` in this crate.
+pub trait Accumulate<F: FieldName, T, I>: FieldState<F, T> {
+    /// The state of the field after adding the item
+    type Output: Init<F, T>;
+
+    /// Adds an item to the field
+    fn push(self, item: I) -> Self::Output;
+}
+
+/// This is used by partial-init-derive to convert the fields of
+/// a `Dyn*Builder` back into the state of a `Partial*` type
+/// 
+/// A state that is initialized needs a value, an uninitialized state
+/// needs no value, and `Option` takes anything.
+pub trait FromOption<F: FieldName, T>: MaybeInit<F, T> + Sized {
+    /// Creates the state from the value of the field, or gives
+    /// back the value if this state can't hold it
+    fn from_option(value: Option<T>) -> Result<Self, Option<T>>;
+}
+
+/// This is implemented by partial-init-derive for every state of a `Partial*` type,
+/// to convert the corrosponding `Dyn*Builder` into that state
+pub trait FromDynamic<D>: Sized {
+    /// Converts the dynamic builder into this state, if any of the fields
+    /// don't match this state then the builder is given back unchanged
+    fn from_dynamic(dynamic: D) -> Result<Self, IntoPartialError<D>>;
+}
+
 /// A zero-sized type that represents uninitailzed values
 /// In the type system
 pub struct Uninit<U: FieldName, T>(std::marker::PhantomData<(U, T)>);
@@ -83,12 +265,32 @@ impl<U: FieldName, T> Clone for Uninit<U, T> {
     }
 }
 
+/// This is used by the code that partial-init-derive creates with its `serde` feature,
+/// to give a clear error if the `serde` feature of this crate isn't enabled too
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_serde {
+    () => {};
+}
+
+/// This is used by the code that partial-init-derive creates with its `serde` feature,
+/// to give a clear error if the `serde` feature of this crate isn't enabled too
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_serde {
+    () => {
+        compile_error!("the `serde` feature of `partial-init-derive` needs the `serde` feature of `partial-init-core` to be enabled too");
+    };
+}
+
 #[macro_export]
 macro_rules! init {
     ($name:ident {
         $($field:ident: $value:expr),*
     }) => {
-        $name::uninit()$(.$field($value))*.build()
+        $crate::Build::build($name::uninit()$(.$field($value))*)
     };
     ($(
         $name:ident {
@@ -0,0 +1,74 @@
+//! Support for nested builders in optional fields
+//!
+//! The FIFs created by `#[nested]` build the inner value right away, which gives either the
+//! value, or a `Result` if the inner type has validators. With `#[strip_option]` the field
+//! is an `Option`, so the built value is put in an `InitSome`, which wraps it in `Some`
+//! when the outer value is built, and passes any error on to `try_build_fallible`.
+
+use super::*;
+
+/// The state of an `Option<T>` field that is initialized with `Some` of the value of `state`
+pub struct InitSome<T, S> {
+    state: S,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, S> InitSome<T, S> {
+    /// Creates a state that initializes the field with `Some` of the value of `state`
+    #[inline]
+    pub fn new(state: S) -> Self {
+        InitSome { state, marker: PhantomData }
+    }
+}
+
+// `T` is a parameter of `InitSome` so that these don't overlap, see `FieldState`
+impl<F: FieldName, T, S: FieldState<F, T>> FieldState<F, Option<T>> for InitSome<T, S> {}
+
+impl<F: FieldName, T, S: Init<F, T>> Init<F, Option<T>> for InitSome<T, S> {
+    #[inline(always)]
+    fn get(self) -> Option<T> {
+        Some(Init::<F, T>::get(self.state))
+    }
+}
+
+impl<F: FieldName, T, S: MaybeInit<F, T>> MaybeInit<F, Option<T>> for InitSome<T, S> {
+    #[inline(always)]
+    fn get(self) -> Option<Option<T>> {
+        MaybeInit::<F, T>::get(self.state).map(Some)
+    }
+}
+
+impl<F: FieldName, T, S: TryInit<F, T, E>, E> TryInit<F, Option<T>, E> for InitSome<T, S> {
+    #[inline(always)]
+    fn try_get(self) -> Result<Option<Option<T>>, E> {
+        self.state.try_get().map(|value| value.map(Some))
+    }
+}
+
+// the inner value was already built, so there is nothing to wait for
+impl<F: FieldName, T, S: Init<F, T>> AsyncInit<F, Option<T>> for InitSome<T, S> {
+    type Future = future::Ready<Option<T>>;
+
+    #[inline(always)]
+    fn get(self) -> Self::Future {
+        future::ready(Init::<F, Option<T>>::get(self))
+    }
+}
+
+impl<F: FieldName, T, S: Init<F, T>> AsyncMaybeInit<F, Option<T>> for InitSome<T, S> {
+    type Future = future::Ready<Option<Option<T>>>;
+
+    #[inline(always)]
+    fn get(self) -> Self::Future {
+        future::ready(Some(Init::<F, Option<T>>::get(self)))
+    }
+}
+
+impl<F: FieldName, T, S: Init<F, T>, R: MaybeInit<F, Option<T>>> Merge<F, Option<T>, R> for InitSome<T, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn merge(self, _: R) -> Self {
+        self
+    }
+}
@@ -0,0 +1,165 @@
+//! Support for fields that fall back to another value
+//!
+//! The `set_{field}` and `{field}_or` functions created by partial-init-derive put an `Or`
+//! in the field, which holds the old state of the field alongside the new value. Neither of
+//! them is read until the value is built, and the new value is only read if the old state
+//! isn't initialized, so sources like `fn() -> T` or `Lazy` are only run if they are needed.
+
+use super::*;
+
+use std::pin::Pin;
+use future::{Context, Future, Poll};
+
+/// The state of a field that uses the value of `state` if it is initialized,
+/// and the value of `fallback` other-wise
+///
+/// This is initialized if `fallback` is initialized, and can be read in the same ways
+/// that both `state` and `fallback` can be read, for example if `fallback` is a `Lazy`
+/// then this can only be read by `build_async`.
+pub struct Or<T, S, V> {
+    state: S,
+    fallback: V,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, S, V> Or<T, S, V> {
+    /// Creates a state that uses the value of `state` if it is initialized,
+    /// and the value of `fallback` other-wise
+    #[inline]
+    pub fn new(state: S, fallback: V) -> Self {
+        Or { state, fallback, marker: PhantomData }
+    }
+}
+
+// `T` is a parameter of `Or` so that these don't overlap, see `FieldState`
+impl<F: FieldName, T, S: FieldState<F, T>, V: FieldState<F, T>> FieldState<F, T> for Or<T, S, V> {}
+
+impl<F: FieldName, T, S: MaybeInit<F, T>, V: Init<F, T>> Init<F, T> for Or<T, S, V> {
+    #[inline]
+    fn get(self) -> T {
+        let fallback = self.fallback;
+
+        MaybeInit::<F, T>::get(self.state).unwrap_or_else(|| Init::<F, T>::get(fallback))
+    }
+}
+
+impl<F: FieldName, T, S: MaybeInit<F, T>, V: MaybeInit<F, T>> MaybeInit<F, T> for Or<T, S, V> {
+    #[inline]
+    fn get(self) -> Option<T> {
+        let fallback = self.fallback;
+
+        MaybeInit::<F, T>::get(self.state).or_else(|| MaybeInit::<F, T>::get(fallback))
+    }
+}
+
+impl<F: FieldName, T, S: TryInit<F, T, E>, V: TryInit<F, T, E>, E> TryInit<F, T, E> for Or<T, S, V> {
+    #[inline]
+    fn try_get(self) -> Result<Option<T>, E> {
+        match self.state.try_get()? {
+            Some(value) => Ok(Some(value)),
+            None => self.fallback.try_get(),
+        }
+    }
+}
+
+impl<F: FieldName, T, S: AsyncMaybeInit<F, T>, V: AsyncInit<F, T>> AsyncInit<F, T> for Or<T, S, V> {
+    type Future = OrFuture<S::Future, V, V::Future>;
+
+    #[inline]
+    fn get(self) -> Self::Future {
+        OrFuture::new(AsyncMaybeInit::<F, T>::get(self.state), self.fallback, AsyncInit::<F, T>::get)
+    }
+}
+
+impl<F: FieldName, T, S: AsyncMaybeInit<F, T>, V: AsyncMaybeInit<F, T>> AsyncMaybeInit<F, T> for Or<T, S, V> {
+    type Future = OrFuture<S::Future, V, V::Future>;
+
+    #[inline]
+    fn get(self) -> Self::Future {
+        OrFuture::new(AsyncMaybeInit::<F, T>::get(self.state), self.fallback, AsyncMaybeInit::<F, T>::get)
+    }
+}
+
+// `state` wins if it is initialized, so `other` only has to be merged into `fallback`
+impl<F: FieldName, T, S, V, R> Merge<F, T, R> for Or<T, S, V>
+where
+    S: MaybeInit<F, T>,
+    V: Merge<F, T, R>,
+    R: MaybeInit<F, T>,
+{
+    type Output = Or<T, S, V::Output>;
+
+    #[inline]
+    fn merge(self, other: R) -> Self::Output {
+        Or::new(self.state, self.fallback.merge(other))
+    }
+}
+
+impl<F: FieldName, T, S, V: Init<F, T>, I> Accumulate<F, T, I> for Or<T, S, V>
+where
+    T: Default + Extend<I> + IntoIterator + Extend<<T as IntoIterator>::Item>,
+    S: MaybeInit<F, T>,
+{
+    type Output = Accumulated<T, Self>;
+
+    #[inline(always)]
+    fn push(self, item: I) -> Accumulated<T, Self> {
+        Accumulated::new(self, item)
+    }
+}
+
+/// The future returned by `AsyncInit::get` and `AsyncMaybeInit::get` on `Or`,
+/// it only gets the future of the fallback if the first future doesn't produce a value
+pub struct OrFuture<A, V, B> {
+    state: OrFutureState<A, V, B>,
+    get_fallback: fn(V) -> B,
+}
+
+enum OrFutureState<A, V, B> {
+    First(A, Option<V>),
+    Fallback(B),
+}
+
+impl<A, V, B> OrFuture<A, V, B> {
+    #[inline]
+    fn new(first: A, fallback: V, get: fn(V) -> B) -> Self {
+        OrFuture {
+            state: OrFutureState::First(first, Some(fallback)),
+            get_fallback: get,
+        }
+    }
+}
+
+// the fallback is never pinned, it is moved out before its future is created
+impl<A: Unpin, V, B: Unpin> Unpin for OrFuture<A, V, B> {}
+
+// the output of the first future is converted with `From`, so that this works for both
+// `AsyncInit` (`T` from `T`) and `AsyncMaybeInit` (`Option<T>` from `T`)
+impl<A, V, B, T> Future for OrFuture<A, V, B>
+where
+    A: Future<Output = Option<T>> + Unpin,
+    B: Future + Unpin,
+    B::Output: From<T>,
+{
+    type Output = B::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<B::Output> {
+        let this = &mut *self;
+
+        if let OrFutureState::First(first, fallback) = &mut this.state {
+            match Pin::new(first).poll(cx) {
+                Poll::Ready(Some(value)) => return Poll::Ready(B::Output::from(value)),
+                Poll::Ready(None) => {
+                    let fallback = fallback.take().expect("the fallback is only taken once");
+                    this.state = OrFutureState::Fallback((this.get_fallback)(fallback));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match &mut this.state {
+            OrFutureState::Fallback(fallback) => Pin::new(fallback).poll(cx),
+            OrFutureState::First(..) => unreachable!(),
+        }
+    }
+}
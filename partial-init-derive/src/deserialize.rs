@@ -0,0 +1,163 @@
+use super::*;
+
+// Implements `Deserialize` for every state of the `Partial*` type, the fields are read
+// from a map into `Option`s and then converted to the state with `FromOption`
+pub(crate) fn deserialize_impl(
+    partial_name: &Ident,
+    internal_module: &Ident,
+
+    field_names: &List<Ident>,
+    fields: &List<syn::Field>,
+    hidden: &[bool],
+
+    ast_generics: &syn::Generics,
+    generic_args: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let where_clause = deserialize_where_clause(fields, ast_generics);
+    let partial_name_str = partial_name.to_string();
+
+    let mut impl_generics = quote! { 'de, };
+    impl_generics.extend(ast_generics.lifetimes().map(quote_one));
+    impl_generics.extend(type_and_const_params(ast_generics).map(quote_param));
+    impl_generics.extend(field_names.iter().zip(fields.iter().map(|field| &field.ty)).map(|(field, ty)| quote! {
+        #field: ::partial_init_core::FromOption<#internal_module::#field, #ty>,
+    }));
+
+    // fields that are hidden by their visibility aren't in `FIELDS`, so they are rejected like
+    // unknown fields, and are always left unset
+    let visible: List<_> = field_names.iter().zip(hidden).filter(|(_, &hidden)| !hidden).map(|(field, _)| field).collect();
+
+    let field_names_list: List<_> = visible
+        .iter()
+        .map(|field| quote! { <#internal_module::#field as ::partial_init_core::FieldName>::NAME })
+        .collect();
+
+    let mut values = quote! {};
+    values.extend(field_names.iter().zip(fields.iter().map(|field| &field.ty)).zip(hidden).map(|((field, ty), hidden)| {
+        if *hidden {
+            quote! { let #field: Option<#ty> = None; }
+        } else {
+            quote! { let mut #field: Option<#ty> = None; }
+        }
+    }));
+
+    let mut arms = quote! {};
+    arms.extend(visible.iter().enumerate().map(|(i, field)| quote! {
+        #i => {
+            if #field.is_some() {
+                return Err(<__A::Error as ::partial_init_core::serde::de::Error>::duplicate_field(
+                    <#internal_module::#field as ::partial_init_core::FieldName>::NAME
+                ));
+            }
+            #field = Some(::partial_init_core::serde::de::MapAccess::next_value(&mut __map)?);
+        }
+    }));
+
+    let mut initializer = quote! { __phantom_data__partial_init_: Default::default() };
+    initializer.extend(field_names.iter().map(|field| quote! {
+        ,#field: ::partial_init_core::de::from_option::<#internal_module::#field, _, _, __A::Error>(#field)?
+    }));
+
+    // `FieldSeed` only returns the positions of the fields in `FIELDS`, so if every
+    // field is hidden then no key is accepted
+    let read_field = if visible.is_empty() {
+        quote! { let _ = __index; unreachable!() }
+    } else {
+        quote! {
+            match __index {
+                #arms
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    let read_fields = quote! {
+        let __seed = ::partial_init_core::de::FieldSeed(Self::FIELDS);
+        while let Some(__index) = ::partial_init_core::serde::de::MapAccess::next_key_seed(&mut __map, __seed)? {
+            #read_field
+        }
+    };
+
+    let deserialize_partial = quote! {
+        #[allow(non_camel_case_types)]
+        impl <#impl_generics> ::partial_init_core::de::DeserializePartial<'de> for #partial_name<#generic_args>
+        #where_clause {
+            const FIELDS: &'static [&'static str] = &[#field_names_list];
+
+            fn visit_map<__A>(mut __map: __A) -> Result<Self, __A::Error>
+            where
+                __A: ::partial_init_core::serde::de::MapAccess<'de>
+            {
+                #values
+                #read_fields
+
+                Ok(#partial_name {
+                    #initializer
+                })
+            }
+        }
+    };
+
+    quote! {
+        #deserialize_partial
+
+        #[allow(non_camel_case_types)]
+        impl <#impl_generics> ::partial_init_core::serde::Deserialize<'de> for #partial_name<#generic_args>
+        #where_clause {
+            #[inline]
+            fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+            where
+                __D: ::partial_init_core::serde::Deserializer<'de>
+            {
+                ::partial_init_core::de::deserialize(#partial_name_str, deserializer)
+            }
+        }
+    }
+}
+
+// The dynamic builder is deserialized through the state of the `Partial*` type where
+// every field is an `Option`
+pub(crate) fn deserialize_dynamic_impl(
+    dynamic_name: &Ident,
+    partial_name: &Ident,
+    fields: &List<syn::Field>,
+
+    ast_generics: &syn::Generics,
+    lifetimes: &List<&syn::Lifetime>,
+    generics: &List<&Ident>,
+) -> proc_macro2::TokenStream {
+    let where_clause = deserialize_where_clause(fields, ast_generics);
+    let (_, ty_generics, _) = ast_generics.split_for_impl();
+
+    let mut impl_generics = quote! { 'de, };
+    impl_generics.extend(ast_generics.lifetimes().map(quote_one));
+    impl_generics.extend(type_and_const_params(ast_generics).map(quote_param));
+
+    let mut optional_state = quote! { #lifetimes #generics };
+    optional_state.extend(fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { Option<#ty>, }
+    }));
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        impl <#impl_generics> ::partial_init_core::serde::Deserialize<'de> for #dynamic_name #ty_generics
+        #where_clause {
+            #[inline]
+            fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+            where
+                __D: ::partial_init_core::serde::Deserializer<'de>
+            {
+                let partial: #partial_name<#optional_state> = ::partial_init_core::serde::Deserialize::deserialize(deserializer)?;
+                Ok(partial.into_dynamic())
+            }
+        }
+    }
+}
+
+fn deserialize_where_clause(fields: &List<syn::Field>, ast_generics: &syn::Generics) -> proc_macro2::TokenStream {
+    extend_where_clause(ast_generics.where_clause.as_ref(), fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { #ty: ::partial_init_core::serde::Deserialize<'de> }
+    }))
+}
@@ -0,0 +1,263 @@
+use super::*;
+
+// Creates the `Dyn*Builder` type for `#[partial_init(dynamic)]`, it uses
+// the `Partial*` type to build the value, so that the semantics of
+// `#[default]` and `#[func_name]` are the same as the static builder
+pub(crate) fn dynamic_builder_impl(
+    type_names: TypeNames,
+    field_list: FieldList,
+    generic_list: GenericList,
+    phantom_data: &List<proc_macro2::TokenStream>,
+    build_fns: &BuildFns,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let TypeNames { vis, name, target_doc, partial_name, internal_module, dynamic_name } = type_names;
+    let FieldList { field_names, fields, field_vis, hidden, .. } = field_list;
+    let GenericList { ast_generics, generic_decl, generic_args, .. } = generic_list;
+    let (impl_generics, ty_generics, where_clause) = ast_generics.split_for_impl();
+    let BuildFns { build, try_build, .. } = build_fns;
+
+    // `into_partial` reports every field that doesn't match
+    if fields.len() > MAX_REQUIRED_FIELDS {
+        return Err(Error::new(
+            name.span(),
+            format!("at most {} fields can be used with `#[partial_init(dynamic)]`", MAX_REQUIRED_FIELDS),
+        ));
+    }
+
+    let struct_fields: List<_> = field_names
+        .iter()
+        .zip(fields.iter().map(|field| &field.ty))
+        .map(|(field, ty)| quote! { #field: Option<#ty> })
+        .collect();
+
+    let mut empty_initializer = quote! { __phantom_data__partial_init_: Default::default() };
+    empty_initializer.extend(field_names.iter().map(|field| quote! { ,#field: None }));
+
+    let mut partial_initializer = quote! { __phantom_data__partial_init_: Default::default() };
+    partial_initializer.extend(field_names.iter().map(|field| quote! { ,#field: self.#field }));
+
+    let mut setters = quote! {};
+    setters.extend(field_names.iter().zip(fields.iter().map(|field| &field.ty)).zip(field_vis).map(|((field, ty), vis)| {
+        let setter_doc = format! {
+            " This is a setter, it takes a `{}` and sets `{}`, passing `None` unsets it",
+            quote! { #ty }, quote! { #field }
+        };
+
+        quote! {
+            #[doc = #setter_doc]
+            #[inline(always)]
+            #vis fn #field<#field: ::partial_init_core::MaybeInit<#internal_module::#field, #ty>>(&mut self, #field: #field) -> &mut Self {
+                self.#field = ::partial_init_core::MaybeInit::get(#field);
+                self
+            }
+        }
+    }));
+
+    // `Box<dyn Any>` can only be downcast to `'static` types
+    let set_field_where_clause = extend_where_clause(where_clause, fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { #ty: 'static }
+    }));
+
+    // Stuff that goes into `FromDynamic`, each field is converted with `FromOption`
+    // and if any of them fail then the builder is put back together
+    let (from_dynamic_generics, from_dynamic_body) = {
+        let mut generics = quote! {};
+        generics.extend(ast_generics.lifetimes().map(quote_one));
+        generics.extend(type_and_const_params(ast_generics).map(quote_param));
+        generics.extend(field_names.iter().zip(fields.iter().map(|field| &field.ty)).map(|(field, ty)| quote! {
+            #field: ::partial_init_core::FromOption<#internal_module::#field, #ty>,
+        }));
+
+        let field_names_list: List<_> = field_names
+            .iter()
+            .map(|field| quote! { <#internal_module::#field as ::partial_init_core::FieldName>::NAME })
+            .collect();
+        let ok_values: List<_> = field_names.iter().map(|field| quote! { Ok(#field) }).collect();
+
+        let mut values = quote! {};
+        values.extend(field_names.iter().map(|field| quote! {
+            let #field = ::partial_init_core::FromOption::from_option(dynamic.#field);
+        }));
+
+        let mut mismatched = quote! {};
+        mismatched.extend(field_names.iter().enumerate().map(|(i, field)| quote! {
+            if #field.is_err() {
+                fields.insert(#i);
+            }
+        }));
+
+        let mut partial_initializer = quote! { __phantom_data__partial_init_: Default::default() };
+        partial_initializer.extend(field_names.iter().map(|field| quote! { ,#field }));
+
+        let mut dynamic_initializer = quote! { __phantom_data__partial_init_: Default::default() };
+        dynamic_initializer.extend(field_names.iter().map(|field| quote! {
+            ,#field: match #field {
+                Ok(#field) => ::partial_init_core::MaybeInit::get(#field),
+                Err(#field) => #field,
+            }
+        }));
+
+        let body = quote! {
+            const FIELDS: &'static [&'static str] = &[#field_names_list];
+
+            #values
+            match (#field_names,) {
+                (#ok_values,) => Ok(#partial_name { #partial_initializer }),
+                (#field_names,) => {
+                    let mut fields = ::partial_init_core::MissingFields::new(FIELDS);
+                    #mismatched
+                    Err(::partial_init_core::IntoPartialError {
+                        builder: #dynamic_name { #dynamic_initializer },
+                        fields,
+                    })
+                }
+            }
+        };
+
+        (generics, body)
+    };
+
+    let mut dynamic_from_partial = quote! { __phantom_data__partial_init_: Default::default() };
+    dynamic_from_partial.extend(field_names.iter().map(|field| quote! {
+        ,#field: ::partial_init_core::MaybeInit::get(self.#field)
+    }));
+
+    let mut set_field_arms = quote! {};
+    // fields that are hidden by their visibility can't be set by name
+    set_field_arms.extend(field_names.iter().zip(fields.iter().map(|field| &field.ty)).zip(hidden).filter(|(_, &hidden)| !hidden).map(|((field, ty), _)| {
+        quote! {
+            <#internal_module::#field as ::partial_init_core::FieldName>::NAME => match value.downcast::<#ty>() {
+                Ok(value) => {
+                    self.#field = Some(*value);
+                    Ok(())
+                }
+                Err(_) => Err(::partial_init_core::BuildError::WrongType(
+                    <#internal_module::#field as ::partial_init_core::FieldName>::INFO
+                )),
+            },
+        }
+    }));
+
+    let dynamic_doc = format! {
+        " This type is a run-time checked builder for `{}`, each field can be set",
+        target_doc
+    };
+
+    let build_doc = format! {
+        " It is the final step in creating a `{}`, if any fields without a default",
+        target_doc
+    };
+
+    let into_partial_doc = format! {
+        " It converts this builder into a `{}`, the state must match which",
+        quote! { #partial_name }
+    };
+
+    let into_dynamic_doc = format! {
+        " It converts this into a `{}`, which can be used",
+        quote! { #dynamic_name }
+    };
+
+    Ok(quote! {
+        /// This type was created by the parital-init-derive crate, see that documentation
+        /// for detail on how this operation works (it's long and detailed)
+        ///
+        #[doc = #dynamic_doc]
+        /// any number of times, and whether the fields are initialized is only checked
+        /// when it is built.
+        #[allow(non_camel_case_types)]
+        #vis struct #dynamic_name #impl_generics
+        #where_clause {
+            #struct_fields,
+            __phantom_data__partial_init_: ::partial_init_core::PhantomData<(#phantom_data)>
+        }
+
+        impl #impl_generics Default for #dynamic_name #ty_generics
+        #where_clause {
+            #[inline(always)]
+            fn default() -> Self {
+                #dynamic_name {
+                    #empty_initializer
+                }
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl #impl_generics #dynamic_name #ty_generics
+        #where_clause {
+            /// This function is part of the dynamic builder api.
+            /// It creates a builder with no fields set.
+            #[inline(always)]
+            #vis fn new() -> Self {
+                Default::default()
+            }
+
+            #setters
+
+            /// This function is part of the dynamic builder api.
+            #[doc = #build_doc]
+            /// are not set, then the names of all of those fields are returned.
+            #[inline(always)]
+            #vis fn #build(self) -> Result<#name #ty_generics, ::partial_init_core::BuildError> {
+                #partial_name {
+                    #partial_initializer
+                }
+                .#try_build()
+                .map_err(::partial_init_core::BuildError::from)
+            }
+
+            /// This function is part of the dynamic builder api.
+            #[doc = #into_partial_doc]
+            /// fields that are set, if they don't match then the builder is given back.
+            #[inline(always)]
+            #vis fn into_partial<State>(self) -> Result<State, ::partial_init_core::IntoPartialError<Self>>
+            where
+                State: ::partial_init_core::FromDynamic<Self>
+            {
+                ::partial_init_core::FromDynamic::from_dynamic(self)
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl <#from_dynamic_generics> ::partial_init_core::FromDynamic<#dynamic_name #ty_generics> for #partial_name<#generic_args>
+        #where_clause {
+            #[inline(always)]
+            fn from_dynamic(dynamic: #dynamic_name #ty_generics) -> Result<Self, ::partial_init_core::IntoPartialError<#dynamic_name #ty_generics>> {
+                #from_dynamic_body
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl <#generic_decl> #partial_name<#generic_args>
+        #where_clause {
+            /// This function is part of the builder api.
+            #[doc = #into_dynamic_doc]
+            /// to continue building it with fields that are only known at run-time.
+            #[inline(always)]
+            #vis fn into_dynamic(self) -> #dynamic_name #ty_generics {
+                #dynamic_name {
+                    #dynamic_from_partial
+                }
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl #impl_generics #dynamic_name #ty_generics
+        #set_field_where_clause {
+            /// This function is part of the dynamic builder api.
+            /// It sets the field with the given name, which is the `FieldName::NAME`
+            /// for that field. The value must have the exact type of the field.
+            #vis fn set_field(
+                &mut self,
+                name: &str,
+                value: ::partial_init_core::DynValue,
+            ) -> Result<(), ::partial_init_core::BuildError> {
+                match name {
+                    #set_field_arms
+                    _ => Err(::partial_init_core::BuildError::UnknownField(name.into())),
+                }
+            }
+        }
+    })
+}
@@ -25,18 +25,25 @@
             decides to reorder fields
     * Changing the name of a field (regardless of visibility) is a **major** breaking change 
         * unless the old function name is maintained with the `#[func_name]` attribute.
+    * Adding the first validator or removing the last one is a **major** breaking change,
+      because it changes what `build` and `try_build` return
+    * Adding, removing or changing `partial`, `module`, `build_fn` or `deinit_prefix` in
+      `#[partial_init(..)]` is a **major** breaking change, because it renames public items
+    * Making a field less visible is a **major** breaking change, because it's FIF and FDF
+      become less visible too, and it may need a `#[default]`
 
     **note** \
     The visibility of all types created by this crate is the same as the type it is 
     derived for. This means all of the types of your fields are in the same 
     visibility as your type. For example, if you apply this derive macro to struct `A`, then
-    all types created by this macro will have a `pub(crate)` visibility.
+    all types created by this macro will have a `pub(crate)` visibility. The FIFs and FDFs
+    follow the visibility of each field instead, see *Field visibility* below.
 
     ```rust
     #[derive(PartialInit)]
     pub(crate) struct A {
-        field: i32,
-        other: String
+        pub field: i32,
+        pub other: String
     }
     ```
 
@@ -47,10 +54,10 @@
     ```rust
     #[derive(PartialInit)]
     pub struct Spell<'a, E: Effect + ?Sized> {
-        with_name: String,
-        damage: u32,
-        range: u32,
-        effects: Vec<&'a E>,
+        pub with_name: String,
+        pub damage: u32,
+        pub range: u32,
+        pub effects: Vec<&'a E>,
     }
 
     trait Effect {  }
@@ -113,10 +120,10 @@
     #[derive(PartialInit)]
     pub struct Spell<'a, E: Effect + ?Sized> {
         #[func_name(with_name)]
-        name: String,
-        damage: u32,
-        range: u32,
-        effects: Vec<&'a E>,
+        pub name: String,
+        pub damage: u32,
+        pub range: u32,
+        pub effects: Vec<&'a E>,
     }
     ```
 
@@ -129,12 +136,12 @@
     #[derive(PartialInit)]
     pub struct Spell<'a, E: Effect + ?Sized> {
         #[func_name(with_name)]
-        name: String,
-        damage: u32,
-        range: u32,
+        pub name: String,
+        pub damage: u32,
+        pub range: u32,
 
         #[default]
-        effects: Vec<&'a E>,
+        pub effects: Vec<&'a E>,
     }
     ```
 
@@ -149,15 +156,62 @@
     #[derive(PartialInit)]
     pub struct Spell<'a, E: Effect + ?Sized> {
         #[func_name(with_name)]
-        name: String,
+        pub name: String,
 
-        damage: u32,
+        pub damage: u32,
 
         #[default(defaults::range)]
-        range: u32,
+        pub range: u32,
 
         #[default]
-        effects: Vec<&'a E>,
+        pub effects: Vec<&'a E>,
+    }
+    ```
+
+    Writing a function for every small default gets tedious, so a literal can be given 
+    directly with `#[default = 10]`, and any other expression with `#[default(expr = ...)]`. 
+    Just like the function, the expression is only evaluated in `build` when the field was 
+    not initialized.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Spell<'a, E: Effect + ?Sized> {
+        #[default(expr = "Fireball".to_owned())]
+        pub name: String,
+
+        #[default = 10]
+        pub range: u32,
+
+        #[default(expr = Vec::with_capacity(4))]
+        pub effects: Vec<&'a E>,
+    }
+    ```
+
+    Sometimes a default depends on other fields, for example the display name of a 
+    spell could default to its name. `#[default(with = function_path, depends_on(field, ..))]` 
+    calls the function with a reference to each of the listed fields, in order. Inside 
+    `build` the fields are evaluated so that every field comes after the fields it depends 
+    on, and a cycle in `depends_on` is a compile error. The fields are listed by their 
+    names in the struct, not the names of their FIFs, and tuple fields are named `_0`, `_1`, 
+    and so on.
+
+    ```rust
+    mod defaults {
+        pub fn display_name(name: &String) -> String {
+            format!("The {}", name)
+        }
+    }
+
+    #[derive(PartialInit)]
+    pub struct Spell<'a, E: Effect + ?Sized> {
+        #[func_name(with_name)]
+        pub name: String,
+
+        #[default(with = defaults::display_name, depends_on(name))]
+        pub display_name: String,
+
+        #[default]
+        pub effects: Vec<&'a E>,
     }
     ```
 
@@ -172,15 +226,15 @@
     #[deinit]
     pub struct Spell<'a, E: Effect + ?Sized> {
         #[func_name(with_name)]
-        name: String,
+        pub name: String,
 
-        damage: u32,
+        pub damage: u32,
 
         #[default(defaults::range)]
-        range: u32,
+        pub range: u32,
 
         #[default]
-        effects: Vec<&'a E>,
+        pub effects: Vec<&'a E>,
     }
     ```
 
@@ -235,172 +289,908 @@
 
     Lets you use a function to provide a default value.
 
+    `#[default = literal]` or `#[default(expr = expression)]`
+
+    Lets you use an expression to provide a default value.
+
+    `#[default(with = function_path, depends_on(field, ..))]`
+
+    Lets you use a function that takes references to other fields to provide a default value.
+
     `#[deinit]`
 
     Lets you deinitialize fields
 
-    ## Reading error messages
+    `#[into]`
 
-    Let's say I forgot to initialize damage, like so
+    Lets a FIF take anything that converts into the type of it's field
+
+    `#[strip_option]`
+
+    Lets the FIF of an `Option<T>` field take a `T`, and adds a `maybe_{name}` FIF
+
+    `#[each = "name"]` or `#[each(name)]`
+
+    Adds a FIF that adds one item to a collection field
+
+    `#[nested]`
+
+    Lets a FIF initialize the fields of another type that derives `PartialInit`
+
+    `#[skip]` or `#[skip(path)]`
+
+    Leaves a field out of the builder, it's always set to it's default when the value is built
+
+    `#[validate(path)]` and `#[partial_init(validate = path)]`
+
+    Lets you check fields, or the whole value, in `build`
+
+    `#[partial_init(dynamic)]`
+
+    Creates a run-time checked `Dyn{name}Builder` alongside `Partial{name}`
+
+    `#[partial_init(prefix = "prefix", rename_all = "rule")]`
+
+    Changes the names of all of the FIFs that don't have a `#[func_name]`
+
+    `#[partial_init(partial = Name, module = name, build_fn = name, deinit_prefix = "prefix")]`
+
+    Changes the names of the `Partial*` type, the `__*__` module, `build` and the FDFs
+
+    `#[partial_init(vis = "pub")]`
+
+    Changes the visibility of the FIFs and FDFs of one field, or of every field
+
+    ## Naming the FIFs
+
+    Instead of adding `#[func_name]` to every field, the names of all of the FIFs can be 
+    changed at once with `#[partial_init(prefix = "with_")]`, which adds a prefix to the name 
+    of every field, and `#[partial_init(rename_all = "camelCase")]`, which changes the case of 
+    every field. The rule is applied before the prefix, and it can be one of `"snake_case"`, 
+    `"camelCase"`, `"PascalCase"`, `"SCREAMING_SNAKE_CASE"`, `"lowercase"` or `"UPPERCASE"`. 
+    Fields with `#[func_name]` keep the name they were given.
 
     ```rust
+    #[derive(PartialInit)]
+    #[deinit]
+    #[partial_init(prefix = "with_")]
+    pub struct Spell {
+        pub name: String,
+
+        #[func_name(damage)]
+        pub base_damage: u32,
+    }
+
     let spell = Spell::uninit()
-                    .with_name("Fireball".to_owned())
-                    .range(50)
-                    .effects(vec!["burning"])
-                    .build();
+        .with_name("Fireball".to_owned())
+        .damage(20)
+        .deinit_with_name()
+        .with_name("Ice Bolt".to_owned())
+        .build();
     ```
 
-    Then I will get the following error message
+    The new name is used everywhere that the name from `#[func_name]` would be, so the FDFs 
+    become `deinit_with_name`, and the marker type is `__Spell__::with_name`. Only the functions 
+    are renamed, so the `NAME` of `__Spell__::with_name` is still `"name"`, and the keys used by 
+    serde and `set_field` are still the names of the fields. If two functions end up with the 
+    same name, for example because `#[func_name]` gives a field the name that the prefix gave 
+    another field, then that is an error.
 
-    ```
-    error[E0599]: no method named `build` found for type `PartialSpell<'_, str, &str, 
-    partial_init_core::Uninit<__Spell__::damage, u32>, u32, std::vec::Vec<&str>>` in 
-    the current scope
-    --> src\main.rs:135:24
-        |
-    107 | #[derive(PartialInit)]
-        |                    - method `build` not found for this
-    ...
-    135 |                       .build();
-        |                        ^^^^^
-        |
-        = note: the method `build` exists but the following trait bounds were not 
-        satisfied:
-                `partial_init_core::Uninit<__Spell__::damage, u32> : 
-                partial_init_core::Init<__Spell__::damage, u32>`
-    ```
+    ## Naming the generated types
 
-    If you see the note at the bottom , we see that 
-    `partial_init_core::Uninit<__Spell__::damage, u32> : partial_init_core::Init<__Spell__::damage, u32>`
-    was not satsified. What this means is that we forgot to initialize `damage`, to an 
-    `u32`. The error message spells it out for us. Yay for hijacking the type system 
-    to get good error messages!
+    The names `Partial{name}`, `__{name}__`, `build` and `deinit_{name}` can clash with other 
+    types in the same module, or with fields that are called `build`. They can be changed 
+    with `#[partial_init(partial = Name, module = name, build_fn = name, deinit_prefix = "prefix")]`.
 
-    Now what if we accidentily give damage a `i32` instead of the `u32` it is looking 
-    for. Then we get:
+    ```rust
+    #[derive(PartialInit)]
+    #[deinit]
+    #[partial_init(partial = SpellBuilder, module = spell_fields, build_fn = finish, deinit_prefix = "clear_")]
+    pub struct Spell {
+        pub name: String,
+        pub build: u32,
+    }
 
+    let builder: SpellBuilder<String, spell_fields::uninit::build> = Spell::uninit()
+        .name("Fireball".to_owned());
+
+    let spell = builder
+        .build(20)
+        .clear_build()
+        .build(30)
+        .finish();
     ```
-    error[E0277]: the trait bound `i32: partial_init_core::Init<__Spell__::damage, 
-    u32>` is not satisfied
-    --> src\main.rs:132:24
-        |
-    132 |                       .damage(20i32)
-        |                        ^^^^^^ the trait 
-        `partial_init_core::Init<__Spell__::damage, u32>` is not implemented for `i32`
+
+    `build_fn` renames every final step of the builder api, so `try_build`, `try_build_fallible` 
+    and `build_async` become `try_finish`, `try_finish_fallible` and `finish_async`, and the 
+    `build` of a `Dyn*Builder` becomes `finish`. `init!` and `#[nested]` still work, because 
+    they go through `partial_init_core::Build` instead of calling `build` directly.
+
+    For enums, `partial` and `module` must be put on each variant, because every variant 
+    has its own `Partial*` type and `__*__` module. The other arguments can be put on the enum, 
+    and then they apply to every variant.
+
+    If a FIF or FDF has the same name as a function in the builder api, or as another FIF or FDF, 
+    then that is an error. With `#[partial_init(dynamic)]` this includes `new`, `set_field` and 
+    `into_partial`, because the setters of the `Dyn*Builder` are named after the FIFs. It is also an error if the `Partial*` type or the `__*__` module has 
+    the same name as the type itself, or as each other.
+
+    ## Field visibility
+
+    The FIFs and FDFs of a field have the same visibility as that field, but they are never 
+    more visible than the type. This way, code that can't see a field can't set it through 
+    the builder either. Because that code still has to be able to call `build`, every field 
+    that is less visible than the type must have a `#[default]`.
+
+    ```rust
+    #[derive(PartialInit)]
+    #[deinit]
+    pub struct Config {
+        pub host: String,
+
+        #[default]
+        pub(crate) retries: u32,
+
+        #[default]
+        #[partial_init(vis = "pub")]
+        pub(crate) port: u16,
+    }
+
+    // in another crate, `retries` and `deinit_retries` can't be used
+    let config = Config::uninit()
+        .host("localhost".to_owned())
+        .port(8080)
+        .build();
     ```
 
-    Which says `'partial_init_core::Init<__Spell__::damage, u32>' is not implemented for 'i32'`
-    meaning we can't initialize a `u32` with a `i32`.
+    The visibility of a single field's FIFs and FDFs can be changed with 
+    `#[partial_init(vis = "pub(crate)")]` on that field, and `#[partial_init(vis = "pub")]` on 
+    the type changes it for every field, which gives back the old behaviour where every function 
+    has the visibility of the type. An empty string, `vis = ""`, makes them private. A visibility 
+    that is more than the type's is lowered to the type's. The fields of enum variants have the 
+    visibility of the enum.
 
-    ## A deep dive into the inner workings
+    The `Dyn*Builder` follows the same rules for it's setters, and fields that are hidden this way 
+    are treated as unknown fields by `set_field`, and when deserializing a `Partial*` type or a 
+    `Dyn*Builder` with the `serde` feature.
 
-    This is all of the code (with documentation stripped for brevity) that is 
-    generated by this macro for the final `Spell` struct. This section will be a 
-    walkthrough on how it works and why it was created.
+    In earlier versions every FIF and FDF had the visibility of the type, so code that used to 
+    compile can now fail with "this field is less visible than `Config`, so it must have a 
+    `#[default]`", or with a FIF that is now private to the module of the type. To upgrade, either:
 
-    Seeing this, we can see the biggest downside to this crate, the longer build 
-    times. But with longer build times you get better error messages and safer, faster 
-    code.
+    * add `#[partial_init(vis = "pub")]` (or the visibility of the type) to the type, which keeps 
+      the old behaviour for every field
+    * add `#[partial_init(vis = "..")]` to the fields that have to be set from outside
+    * or give the hidden fields a `#[default]`, or make them as visible as the type
+
+    ## Tuple structs
+
+    Tuple structs work too. Because their fields don't have names, each FIF is named
+    after the position of it's field, `_0`, `_1`, and so on. `#[func_name]` can be used
+    to give them a better name.
 
     ```rust
-    #[allow(non_camel_case_types)]
-    pub mod __Spell__ {
-        pub enum with_name {}
-        impl ::partial_init_core::FieldName for with_name {}
-        pub enum damage {}
-        impl ::partial_init_core::FieldName for damage {}
-        pub enum range {}
-        impl ::partial_init_core::FieldName for range {}
-        pub enum effects {}
-        impl ::partial_init_core::FieldName for effects {}
-        pub mod uninit {
-            use super::super::*;
-            pub type with_name = ::partial_init_core::Uninit<super::with_name, String>;
-            pub type damage = ::partial_init_core::Uninit<super::damage, u32>;
-            pub type range = ::partial_init_core::Uninit<super::range, u32>;
-            pub type effects<'a, E> = ::partial_init_core::Uninit<super::effects, Vec<&'a E>>;
+    #[derive(PartialInit)]
+    pub struct Rgb(#[func_name(red)] pub u8, #[func_name(green)] pub u8, #[default] pub u8);
+
+    let color = init! {
+        Rgb {
+            red: 255,
+            green: 128,
+            _2: 0
         }
+    };
+    ```
+
+    Note that reordering the fields of a tuple struct changes the names of the FIFs that 
+    don't use `#[func_name]`, so it is a **major** breaking change.
+
+    ## Const generics
+
+    Const generic parameters work like type parameters, they are kept on `Partial{name}`, 
+    after the lifetimes and type parameters, and on the types in `__{name}__::uninit` 
+    that use them.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Buffer<T, const N: usize> {
+        pub data: [T; N],
+
+        #[default]
+        pub len: usize,
     }
-    #[allow(non_camel_case_types)]
-    #[derive(Clone, Copy)]
-    pub struct PartialSpell<
-        'a,
-        E: Effect + ?Sized,
-        with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-        damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-        range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-        effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
-    > {
-        with_name: with_name,
-        damage: damage,
-        range: range,
-        effects: effects,
-        __phantom_data__partial_init_: ::partial_init_core::PhantomData<(String, u32, u32, Vec<&'a E>)>,
+
+    let buffer = Buffer::uninit()
+        .data([0u8; 16])
+        .build();
+
+    let uninit: __Buffer__::uninit::data<u8, 16> = Default::default();
+    ```
+
+    Const parameters have to be declared after all of the type parameters, and they can't 
+    have defaults, because the version of `syn` that this crate uses can't parse them.
+
+    ## Enums
+
+    Enums get one `Partial*` type for each variant that has fields, named 
+    `Partial{name}{variant}`, with it's meta-data in the module `__{name}{variant}__`. 
+    Since there is more than one way to start building an enum, it doesn't implement 
+    `PartialInit`, instead each variant gets a function named `uninit_{variant}` (in 
+    snake case). Unit variants are skipped, because they have nothing to initialize.
+    `#[deinit]` can be put on the enum, on a variant, or on the fields of a variant.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub enum Message {
+        Login {
+            user: String,
+            #[default]
+            password: Option<String>,
+        },
+        Logout(u32),
+        Quit,
     }
-    #[allow(non_camel_case_types)]
-    impl<'a, E: Effect + ?Sized> ::partial_init_core::PartialInit for Spell<'a, E> {
-        type Uninitialized = PartialSpell<
-            'a,
-            E,
-            ::partial_init_core::Uninit<__Spell__::with_name, String>,
-            ::partial_init_core::Uninit<__Spell__::damage, u32>,
-            ::partial_init_core::Uninit<__Spell__::range, u32>,
-            ::partial_init_core::Uninit<__Spell__::effects, Vec<&'a E>>,
-        >;
-        #[inline(always)]
-        fn uninit() -> Self::Uninitialized {
-            Default::default()
-        }
+
+    let login: Message = Message::uninit_login()
+                            .user("Ozaren".to_owned())
+                            .build();
+
+    let logout: Message = Message::uninit_logout()
+                            ._0(10)
+                            .build();
+    ```
+
+    ## Fallible builds
+
+    `build` only exists when all fields without a default are initialized, but sometimes 
+    you only find out which fields are initialized at run-time. For these cases every 
+    `Partial*` has a `try_build` function, which returns a 
+    `Result<{name}, partial_init_core::MissingFields>`. If any field without a default is 
+    not initialized, the error lists the names of all of those fields. The names come from 
+    `partial_init_core::FieldName::NAME`, which is implemented for each type in `__{name}__`.
+    FIFs accept anything that implements `partial_init_core::MaybeInit`, so an `Option` can
+    be passed to any FIF, but only `try_build` can be used on the result.
+
+    ```rust
+    let name: Option<String> = read_name_from_config();
+
+    let spell = Spell::uninit()
+                    .with_name(name)
+                    .damage(20)
+                    .try_build();
+
+    match spell {
+        Ok(spell) => cast(spell),
+        Err(missing) => println!("{}", missing), // missing fields: `with_name`
     }
-    impl<'a, E: Effect + ?Sized> Default
-        for PartialSpell<
-            'a,
-            E,
-            ::partial_init_core::Uninit<__Spell__::with_name, String>,
-            ::partial_init_core::Uninit<__Spell__::damage, u32>,
-            ::partial_init_core::Uninit<__Spell__::range, u32>,
-            ::partial_init_core::Uninit<__Spell__::effects, Vec<&'a E>>,
-        >
-    {
-        #[inline(always)]
-        fn default() -> Self {
-            PartialSpell {
-                __phantom_data__partial_init_: Default::default(),
-                with_name: Default::default(),
-                damage: Default::default(),
-                range: Default::default(),
-                effects: Default::default(),
-            }
-        }
+    ```
+
+    `MissingFields` can track at most 128 fields, so on a type with more fields without a default 
+    than that, calling `try_build` or `try_build_fallible` is a compile error that explains the limit,
+    but it can still be built with `build`. For the same reason `#[partial_init(dynamic)]` can only be
+    used with at most 128 fields.
+
+    ## Field meta-data
+
+    Each of the types in `__{name}__` implements `partial_init_core::FieldName`, which 
+    describes the field at run-time. `NAME` is the name that the field was declared with, 
+    without the `r#` of a raw identifier, so it isn't changed by `#[func_name]`, `prefix` or 
+    `rename_all`, and the fields of a tuple struct are named `_{index}`. `INDEX` is the position 
+    of the field in the declaration, `TYPE_NAME` is the type of the field as it was written, 
+    and `HAS_DEFAULT` is true if the field is marked `#[default]`. All of these are collected 
+    into `INFO`, and `__{name}__::FIELDS` is a table of the `INFO` of every field.
+
+    ```rust
+    use partial_init_core::FieldName;
+
+    assert_eq!(<__Spell__::range as FieldName>::INDEX, 2);
+
+    for field in __Spell__::FIELDS {
+        println!("{}: {} (has default: {})", field.name, field.type_name, field.has_default);
     }
-    #[allow(non_camel_case_types)]
-    impl<
-            'a,
-            E: Effect + ?Sized,
-            with_name: ::partial_init_core::Init<__Spell__::with_name, String>,
-            damage: ::partial_init_core::Init<__Spell__::damage, u32>,
-            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
-        > PartialSpell<'a, E, with_name, damage, range, effects>
-    {
-        #[inline(always)]
-        pub fn build(self) -> Spell<'a, E> {
-            Spell {
-                name: ::partial_init_core::Init::get(self.with_name),
-                damage: ::partial_init_core::Init::get(self.damage),
-                range: ::partial_init_core::MaybeInit::get(self.range).unwrap_or_else(defaults::range),
-                effects: ::partial_init_core::MaybeInit::get(self.effects).unwrap_or_default(),
-            }
-        }
+    ```
+
+    ## Dynamic builders
+
+    `Partial*` types check that everything is initialized at compile-time, but sometimes 
+    fields are set one by one from data that is only known at run-time. Adding 
+    `#[partial_init(dynamic)]` to the struct (or enum) also creates a `Dyn{name}Builder`, 
+    which stores each field in an `Option`. Each field gets a setter named after it's FIF, 
+    which takes the same values as the FIF, and `set_field` sets a field using it's 
+    `FieldName::NAME` and a `Box<dyn Any>` holding a value with the exact type of the field. 
+    Building is done by `build`, which uses the same defaults as `Partial{name}` and 
+    returns a `Result<{name}, partial_init_core::BuildError>`.
+
+    **note** \
+    Dynamic builders need the `std` feature of `partial-init-core`, and `set_field` can only 
+    be used if all of the field types are `'static`.
+
+    ```rust
+    #[derive(PartialInit)]
+    #[partial_init(dynamic)]
+    pub struct Config {
+        pub host: String,
+        pub port: u16,
+        #[default]
+        pub verbose: bool,
     }
-    #[allow(non_camel_case_types)]
-    impl<
-            'a,
-            E: Effect + ?Sized,
-            damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+
+    let mut builder = DynConfigBuilder::new();
+
+    for (key, value) in plugin_settings {
+        builder.set_field(key, value)?;
+    }
+
+    builder.port(8080);
+    let config: Config = builder.build()?;
+    ```
+
+    A `Partial{name}` in any state can be turned into it's dynamic builder with 
+    `into_dynamic`, and a dynamic builder can be turned back into a `Partial{name}` with 
+    `into_partial`. Each field of the requested state is checked at run-time, an initialized 
+    field must be set, an uninitialized field must not be set, and an `Option` takes 
+    anything. If any field doesn't match, the builder is given back in a 
+    `partial_init_core::IntoPartialError` along with the names of those fields.
+
+    ```rust
+    let mut builder = Config::uninit()
+                        .host("localhost".to_owned())
+                        .into_dynamic();
+
+    apply_overrides(&mut builder);
+
+    let partial: PartialConfig<String, u16, Option<bool>> = builder.into_partial()?;
+    let config = partial.build();
+    ```
+
+    ## Deserializing partial values
+
+    With the `serde` feature enabled on both `partial-init-core` and `partial-init-derive`, 
+    every state of `Partial{name}` implements `serde::Deserialize`. It is read from a map, 
+    where the keys are the names from `partial_init_core::FieldName::NAME`. Each field is 
+    checked against the state, like `into_partial`: an initialized field must be present, an 
+    uninitialized field must be absent, and an `Option` takes anything. Unknown fields and 
+    fields given more than once are reported as errors. If 
+    `#[partial_init(dynamic)]` is used, `Dyn{name}Builder` can be deserialized too. If the feature 
+    is only enabled on `partial-init-derive`, every derive fails with an error that says so.
+
+    ```rust
+    // every field is optional, so any part of a config file can be read
+    type ConfigLayer = PartialConfig<Option<String>, Option<u16>, Option<bool>>;
+
+    let defaults: ConfigLayer = serde_json::from_str(r#"{ "port": 8080 }"#)?;
+    let overlay: ConfigLayer = serde_json::from_str(r#"{ "host": "localhost" }"#)?;
+    ```
+
+    ## Merging partial values
+
+    `Partial{name}` has a `merge` function, which combines two values of the same type 
+    field-by-field. A field is initialized in the result if it is initialized on either 
+    side, and if it is initialized on both sides then the value from `self` wins, and the 
+    value from `other` is dropped without being evaluated. This is done with the 
+    `partial_init_core::Merge` trait, which is implemented for `Uninit`, `Option` and every 
+    initializing type in `partial_init_core`. A field that is an `Option` on the left stays 
+    an `Option`, because whether it is set is only known at run-time, so `try_build` must be 
+    used on the result.
+
+    If you implement `Init` for your own type, then you should also implement `Merge` for it 
+    (with `Output = Self`) so that it can be used with `merge`.
+
+    ```rust
+    // user value if set, else file value, else the default
+    let config = user.merge(file).try_build()?;
+
+    let config = Config::uninit()
+        .port(8080)
+        .merge(Config::uninit().hostname("localhost".to_owned()).port(80))
+        .build(); // port is 8080
+    ```
+
+    ## Validation
+
+    Fields can be checked when the value is built with `#[validate(path)]`, where the path 
+    is a function `fn(&T) -> Result<(), &'static str>`. The whole value can be checked with 
+    `#[partial_init(validate = path)]` on the type, where the path is a function 
+    `fn(&Name) -> Result<(), partial_init_core::ValidationError>`. The validators on the 
+    fields run first, in the order the fields are declared, then the validator on the type.
+
+    If there are any validators, then `build` returns `Result<Name, ValidationError>`, and 
+    `try_build` returns `Result<Name, TryBuildError>`, which is either the missing fields or 
+    the `ValidationError`. The error from a field validator carries the `FieldInfo` of that 
+    field, and a type validator can blame a field with `ValidationError::for_field`.
+
+    ```rust
+    mod checks {
+        use partial_init_core::ValidationError;
+
+        pub fn not_empty(name: &String) -> Result<(), &'static str> {
+            if name.is_empty() { Err("must not be empty") } else { Ok(()) }
+        }
+
+        pub fn spell(spell: &super::Spell) -> Result<(), ValidationError> {
+            if spell.damage > 100 && spell.range > 10 {
+                Err(ValidationError::for_field::<super::__Spell__::range>("too strong"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[derive(PartialInit)]
+    #[partial_init(validate = checks::spell)]
+    pub struct Spell {
+        #[validate(checks::not_empty)]
+        pub name: String,
+        pub damage: u32,
+        pub range: u32,
+    }
+
+    let err = Spell::uninit().name(String::new()).damage(1).range(1).build().unwrap_err();
+    println!("{}", err); // invalid value for field `name`: must not be empty
+    ```
+
+    ## Asynchronous builds
+
+    A field can be initialized with a future, by passing `partial_init_core::Lazy::future(fut)` 
+    to its FIF. Such a value can't be read by `build` or `try_build`, instead `Partial{name}` 
+    has a `build_async` function, which returns a future that polls all of the fields' futures 
+    at the same time, and then builds the value like `build` does, using the defaults and 
+    running the validators. It doesn't depend on any executor, so it can be awaited by any of 
+    them. The futures must be `Unpin`, so `async` blocks must be pinned with `Box::pin`.
+
+    This is why the generic parameters of `Partial{name}` and the FIFs are bounded by 
+    `partial_init_core::FieldState`, which is implemented by every `MaybeInit` type and by 
+    `Lazy`. `build_async` uses the `AsyncInit` and `AsyncMaybeInit` traits, which are 
+    implemented by `Lazy` and every type that implements `Init` in `partial-init-core`. If you 
+    implement `Init` for your own type, then you should also implement `FieldState`, 
+    `AsyncInit` and `AsyncMaybeInit` for it.
+
+    ```rust
+    use partial_init_core::Lazy;
+
+    let spell: Spell<_> = Spell::uninit()
+        .with_name(Lazy::future(Box::pin(fetch_name())))
+        .damage(Lazy::future(Box::pin(fetch_damage())))
+        .range(10)
+        .build_async()
+        .await;
+    ```
+
+    ## Fallible field sources
+
+    A field can be given a source that can fail, a `Result<T, E>`, a `fn() -> Result<T, E>`, 
+    or any of the closure types that `partial-init-core` supports for lazy values, returning 
+    a `Result<T, E>`. These implement `partial_init_core::TryInit<F, T, E>`, which is also 
+    implemented by every `MaybeInit` type in `partial-init-core`, for any `E`. They can't be 
+    read by `build` or `try_build`, instead `Partial{name}` has a `try_build_fallible` 
+    function, which reads the fields in order and returns the first error, as 
+    `TryInitError::Failed`, along with the `FieldInfo` of its field. Missing fields and 
+    validators are handled like `try_build`. All of the fallible sources must have the same 
+    error type.
+
+    ```rust
+    let spell = Spell::uninit()
+        .with_name("Fireball".to_owned())
+        .damage("20".parse::<u32>())
+        .range(read_range as fn() -> Result<u32, ParseIntError>)
+        .try_build_fallible();
+
+    match spell {
+        Ok(spell) => println!("{}", spell.damage),
+        // failed to initialize field `range`: invalid digit found in string
+        Err(err) => println!("{}", err),
+    }
+    ```
+
+    ## Conversions
+
+    By default a field only accepts a value of its own type, or one of the lazy sources. 
+    Wrapping a borrowed value in `partial_init_core::Convert` lets a field of some owned types 
+    accept it, and it is converted when the value is built.
+
+    | field type   | also accepts          |
+    |--------------|-----------------------|
+    | `Box<T>`     | `Convert(T)`          |
+    | `String`     | `Convert(&str)`       |
+    | `CString`    | `Convert(&CStr)`      |
+    | `OsString`   | `Convert(&OsStr)`     |
+    | `PathBuf`    | `Convert(&Path)`      |
+    | `Vec<T>`     | `Convert(&[T])`       |
+
+    ```rust
+    let spell = Spell::uninit()
+        .with_name(Convert("Fireball"))
+        .damage(20)
+        .build();
+    ```
+
+    These are only on `Convert`, and not on the borrowed values themselves, because each of 
+    those impls would give the compiler one more choice for the type of the value passed to a 
+    field. For example if every `T` could initialize a `Box<T>` field, then passing a `Box<u32>` 
+    to a `Box<T>` field of a generic type wouldn't say if `T` is `u32` or `Box<u32>`. 
+    `Convert(&[T])` and `Convert(T)` can also be the state of a field that items are added to 
+    with `#[each = name]`.
+
+    ## Converting values with `#[into]`
+
+    Adding `#[into]` to a field changes its FIF to take anything that implements 
+    `Into<T>`, where `T` is the type of the field, so `.with_name("Fireball")` works for a 
+    `String` field. Like `#[deinit]`, `#[into]` can also be put on the struct, and then it 
+    applies to every field.
+
+    Because a lazy value doesn't implement `Into<T>`, the FIF that takes any value that 
+    `Partial{name}` accepts is still there, named `{name}_with`.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Spell {
+        #[into]
+        #[func_name(with_name)]
+        pub name: String,
+
+        pub damage: u32,
+    }
+
+    let spell = Spell::uninit()
+        .with_name("Fireball")
+        .damage(20)
+        .build();
+
+    let spell = Spell::uninit()
+        .with_name_with(default_name as fn() -> String)
+        .damage(20)
+        .build();
+    ```
+
+    ## Optional fields with `#[strip_option]`
+
+    `Option<T>` fields already accept a `T`, but if `T` is a generic parameter the compiler 
+    can't tell if a value is meant to be the `T` or the `Option<T>`. Adding `#[strip_option]` 
+    to an `Option<T>` field changes its FIF to take exactly a `T`, and adds a 
+    `maybe_{name}` FIF that takes an `Option<T>`. Like with `#[into]`, the FIF that takes 
+    any value is named `{name}_with`, and the two can be combined, so that the FIF takes 
+    anything that converts into a `T`.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Spell<T> {
+        #[func_name(with_name)]
+        pub name: String,
+
+        #[strip_option]
+        #[default]
+        pub bonus: Option<T>,
+    }
+
+    let spell = Spell::uninit()
+        .with_name("Fireball".to_owned())
+        .bonus(10)
+        .build();
+
+    let spell = Spell::uninit()
+        .with_name("Fireball".to_owned())
+        .maybe_bonus(read_bonus())
+        .build();
+    ```
+
+    `#[strip_option]` looks for a type named `Option`, so it doesn't work with type 
+    aliases of `Option`.
+
+    ## Adding items with `#[each]`
+
+    Adding `#[each = "effect"]`, or `#[each(effect)]`, to a collection field like a `Vec`, 
+    `HashMap` or `BTreeSet` creates a FIF named `effect` that adds a single item to the 
+    field. The name has to be a string after `=`, because Rust only allows literals 
+    there. The FIF can be called whether or not the field is initialized. Items added to 
+    an uninitialized field become the value of the field, and items added to an 
+    initialized field are added to it's value when the value is built, so lazy values are 
+    still only read in `build`.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Spell<'a, E: Effect + ?Sized> {
+        #[func_name(with_name)]
+        pub name: String,
+
+        #[each = "effect"]
+        #[default]
+        pub effects: Vec<&'a E>,
+    }
+
+    let spell: Spell<_> = Spell::uninit()
+        .with_name("Fireball".to_owned())
+        .effect("burning")
+        .effect("heat stroke")
+        .build();
+    ```
+
+    The item type is `<T as IntoIterator>::Item`, and the field type must implement `Default` 
+    and `Extend` for it's items. After the first item the state of the field is a 
+    `partial_init_core::Accumulated`, which is always initialized.
+
+    ## Nested builders with `#[nested]`
+
+    If the type of a field derives `PartialInit` too, adding `#[nested]` to the field 
+    changes its FIF to take a function, which is given the uninitialized 
+    `Partial{inner}`, and returns it with its fields initialized. The FIF builds the 
+    inner value right away, so the inner type doesn't have to be built separately. 
+    Like with `#[into]`, the FIF that takes any value is named `{name}_with`, and 
+    `#[nested]` can be combined with `#[strip_option]`.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Cost {
+        pub mana: u32,
+
+        #[default]
+        pub health: u32,
+    }
+
+    #[derive(PartialInit)]
+    pub struct Spell {
+        #[func_name(with_name)]
+        pub name: String,
+
+        #[nested]
+        pub cost: Cost,
+    }
+
+    let spell = Spell::uninit()
+        .with_name("Fireball".to_owned())
+        .cost(|cost| cost.mana(20))
+        .build();
+    ```
+
+    If a field of the inner type is missing, the error points to the FIF, and says which 
+    field is missing in the same way that `build` does, through 
+    `partial_init_core::Build`, which is implemented for every `Partial*` type that can be 
+    built. If the inner type has validators, its `build` returns a `Result`, and the field 
+    holds that `Result` until the outer value is built with `try_build_fallible`, which 
+    reports a rejected inner value as `TryInitError::Failed`, with the `FieldInfo` of the 
+    outer field and the inner `ValidationError`. With `#[strip_option]` the built value is 
+    held in a `partial_init_core::InitSome`, which wraps it in `Some` when it is read.
+
+    ## Skipped fields with `#[skip]`
+
+    Fields that should never be set through the builder, like caches, counters or 
+    `PhantomData`, can be marked with `#[skip]`. A skipped field doesn't get a generic parameter 
+    on `Partial*`, a marker type in `__{name}__`, or any FIFs or FDFs, and it is only filled in 
+    when the value is built. `#[skip]` uses `Default::default()`, and `#[skip(path)]` calls 
+    `path()`, in the same way as `#[default(path)]`.
+
+    ```rust
+    use std::marker::PhantomData;
+
+    fn start_count() -> u32 { 1 }
+
+    #[derive(PartialInit)]
+    pub struct Spell<T> {
+        pub name: String,
+
+        #[skip]
+        cache: Vec<u8>,
+
+        #[skip(start_count)]
+        casts: u32,
+
+        #[skip]
+        marker: PhantomData<T>,
+    }
+
+    let spell: PartialSpell<(), String> = Spell::uninit().name("Fireball".to_owned());
+    let spell = spell.build();
+    ```
+
+    `#[skip]` can't be combined with any other field attribute, and a skipped field can't be 
+    named in `depends_on`. Skipped fields also don't have to be as visible as the type, because 
+    they can't be set anyway. Skipped fields still count towards `FieldName::INDEX` of the 
+    fields after them, so it is always the position of the field in the declaration.
+
+    ## Replacing fields with `set_{field}` and `{field}_or`
+
+    A FIF can only be called while its field is uninitialized, so changing a value needs 
+    `#[deinit]` and a call to the FDF first. Every field also gets `set_{name}`, which works 
+    in any state and always initializes the field with the new value, and `{name}_or`, which 
+    keeps the value of the field if it's initialized when the value is built, and otherwise 
+    uses the new value.
+
+    ```rust
+    #[derive(PartialInit)]
+    pub struct Spell {
+        pub name: String,
+
+        #[default]
+        pub damage: u32,
+    }
+
+    let spell = Spell::uninit()
+        .name("Fireball".to_owned())
+        .set_name("Ice Bolt".to_owned())
+        .damage_or(10)
+        .build();
+
+    let spell = Spell::uninit()
+        .name("Fireball".to_owned())
+        .damage(20)
+        .damage_or(10)
+        .build();
+    ```
+
+    Both take a value that initializes the field, like a `{field_type}` or a `fn() -> {field_type}`, 
+    but not an `Option` or a `Lazy`, so the field is always initialized afterwards. The value is 
+    put in a `partial_init_core::Or` alongside the old state of the field, `set_{name}` drops the 
+    old state first. Nothing is read until the value is built, and the new value is only read if 
+    the old state isn't initialized, so `damage_or(compute_damage as fn() -> u32)` only calls 
+    `compute_damage` if it's needed. The old state is read in the same way as before, so a field 
+    that was set to a `Lazy` still needs `build_async`. With `#[into]` they take anything that 
+    converts into the type of the field, and with `#[strip_option]` they take an `Option`. They 
+    use the name from `#[func_name]`, and get the same visibility as the other FIFs of the field.
+
+    A struct with the fields `x` and `set_x` would get two functions called `set_x`, so this 
+    is an error, and one of them has to be renamed with `#[func_name]`.
+
+    ## Reading error messages
+
+    Let's say I forgot to initialize damage, like so
+
+    ```rust
+    let spell = Spell::uninit()
+                    .with_name("Fireball".to_owned())
+                    .range(50)
+                    .effects(vec!["burning"])
+                    .build();
+    ```
+
+    Then I will get the following error message
+
+    ```
+    error[E0599]: no method named `build` found for type `PartialSpell<'_, str, &str, 
+    partial_init_core::Uninit<__Spell__::damage, u32>, u32, std::vec::Vec<&str>>` in 
+    the current scope
+    --> src\main.rs:135:24
+        |
+    107 | #[derive(PartialInit)]
+        |                    - method `build` not found for this
+    ...
+    135 |                       .build();
+        |                        ^^^^^
+        |
+        = note: the method `build` exists but the following trait bounds were not 
+        satisfied:
+                `partial_init_core::Uninit<__Spell__::damage, u32> : 
+                partial_init_core::Init<__Spell__::damage, u32>`
+    ```
+
+    If you see the note at the bottom , we see that 
+    `partial_init_core::Uninit<__Spell__::damage, u32> : partial_init_core::Init<__Spell__::damage, u32>`
+    was not satsified. What this means is that we forgot to initialize `damage`, to an 
+    `u32`. The error message spells it out for us. Yay for hijacking the type system 
+    to get good error messages!
+
+    Now what if we accidentily give damage a `i32` instead of the `u32` it is looking 
+    for. Then we get:
+
+    ```
+    error[E0277]: the trait bound `i32: partial_init_core::FieldState<__Spell__::damage, 
+    u32>` is not satisfied
+    --> src\main.rs:132:24
+        |
+    132 |                       .damage(20i32)
+        |                        ^^^^^^ the trait 
+        `partial_init_core::FieldState<__Spell__::damage, u32>` is not implemented for `i32`
+    ```
+
+    Which says `'partial_init_core::FieldState<__Spell__::damage, u32>' is not implemented for 'i32'`
+    meaning we can't initialize a `u32` with a `i32`.
+
+    ## A deep dive into the inner workings
+
+    This is all of the code (with documentation stripped for brevity) that is 
+    generated by this macro for the final `Spell` struct. This section will be a 
+    walkthrough on how it works and why it was created.
+
+    Seeing this, we can see the biggest downside to this crate, the longer build 
+    times. But with longer build times you get better error messages and safer, faster 
+    code.
+
+    ```rust
+    #[allow(non_camel_case_types)]
+    pub mod __Spell__ {
+        pub enum with_name {}
+        impl ::partial_init_core::FieldName for with_name {}
+        pub enum damage {}
+        impl ::partial_init_core::FieldName for damage {}
+        pub enum range {}
+        impl ::partial_init_core::FieldName for range {}
+        pub enum effects {}
+        impl ::partial_init_core::FieldName for effects {}
+        pub mod uninit {
+            use super::super::*;
+            pub type with_name = ::partial_init_core::Uninit<super::with_name, String>;
+            pub type damage = ::partial_init_core::Uninit<super::damage, u32>;
+            pub type range = ::partial_init_core::Uninit<super::range, u32>;
+            pub type effects<'a, E> = ::partial_init_core::Uninit<super::effects, Vec<&'a E>>;
+        }
+    }
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy)]
+    pub struct PartialSpell<
+        'a,
+        E: Effect + ?Sized,
+        with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+        damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+        range: ::partial_init_core::FieldState<__Spell__::range, u32>,
+        effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
+    > {
+        with_name: with_name,
+        damage: damage,
+        range: range,
+        effects: effects,
+        __phantom_data__partial_init_: ::partial_init_core::PhantomData<(String, u32, u32, Vec<&'a E>)>,
+    }
+    #[allow(non_camel_case_types)]
+    impl<'a, E: Effect + ?Sized> ::partial_init_core::PartialInit for Spell<'a, E> {
+        type Uninitialized = PartialSpell<
+            'a,
+            E,
+            ::partial_init_core::Uninit<__Spell__::with_name, String>,
+            ::partial_init_core::Uninit<__Spell__::damage, u32>,
+            ::partial_init_core::Uninit<__Spell__::range, u32>,
+            ::partial_init_core::Uninit<__Spell__::effects, Vec<&'a E>>,
+        >;
+        #[inline(always)]
+        fn uninit() -> Self::Uninitialized {
+            Default::default()
+        }
+    }
+    impl<'a, E: Effect + ?Sized> Default
+        for PartialSpell<
+            'a,
+            E,
+            ::partial_init_core::Uninit<__Spell__::with_name, String>,
+            ::partial_init_core::Uninit<__Spell__::damage, u32>,
+            ::partial_init_core::Uninit<__Spell__::range, u32>,
+            ::partial_init_core::Uninit<__Spell__::effects, Vec<&'a E>>,
+        >
+    {
+        #[inline(always)]
+        fn default() -> Self {
+            PartialSpell {
+                __phantom_data__partial_init_: Default::default(),
+                with_name: Default::default(),
+                damage: Default::default(),
+                range: Default::default(),
+                effects: Default::default(),
+            }
+        }
+    }
+    #[allow(non_camel_case_types)]
+    impl<
+            'a,
+            E: Effect + ?Sized,
+            with_name: ::partial_init_core::Init<__Spell__::with_name, String>,
+            damage: ::partial_init_core::Init<__Spell__::damage, u32>,
+            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
+            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+        > PartialSpell<'a, E, with_name, damage, range, effects>
+    {
+        #[inline(always)]
+        pub fn build(self) -> Spell<'a, E> {
+            Spell {
+                name: ::partial_init_core::Init::get(self.with_name),
+                damage: ::partial_init_core::Init::get(self.damage),
+                range: ::partial_init_core::MaybeInit::get(self.range).unwrap_or_else(defaults::range),
+                effects: ::partial_init_core::MaybeInit::get(self.effects).unwrap_or_default(),
+            }
+        }
+    }
+    #[allow(non_camel_case_types)]
+    impl<
+            'a,
+            E: Effect + ?Sized,
+            damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+            range: ::partial_init_core::FieldState<__Spell__::range, u32>,
+            effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
         >
         PartialSpell<
             'a,
@@ -412,7 +1202,7 @@
         >
     {
         #[inline(always)]
-        pub fn with_name<with_name: ::partial_init_core::Init<__Spell__::with_name, String>>(
+        pub fn with_name<with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>>(
             self,
             with_name: with_name,
         ) -> PartialSpell<'a, E, with_name, damage, range, effects> {
@@ -429,9 +1219,9 @@
     impl<
             'a,
             E: Effect + ?Sized,
-            with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+            with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+            range: ::partial_init_core::FieldState<__Spell__::range, u32>,
+            effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
         >
         PartialSpell<
             'a,
@@ -443,7 +1233,7 @@
         >
     {
         #[inline(always)]
-        pub fn damage<damage: ::partial_init_core::Init<__Spell__::damage, u32>>(
+        pub fn damage<damage: ::partial_init_core::FieldState<__Spell__::damage, u32>>(
             self,
             damage: damage,
         ) -> PartialSpell<'a, E, with_name, damage, range, effects> {
@@ -460,9 +1250,9 @@
     impl<
             'a,
             E: Effect + ?Sized,
-            with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-            damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+            with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+            damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+            effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
         >
         PartialSpell<
             'a,
@@ -474,7 +1264,7 @@
         >
     {
         #[inline(always)]
-        pub fn range<range: ::partial_init_core::Init<__Spell__::range, u32>>(
+        pub fn range<range: ::partial_init_core::FieldState<__Spell__::range, u32>>(
             self,
             range: range,
         ) -> PartialSpell<'a, E, with_name, damage, range, effects> {
@@ -491,9 +1281,9 @@
     impl<
             'a,
             E: Effect + ?Sized,
-            with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-            damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
+            with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+            damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+            range: ::partial_init_core::FieldState<__Spell__::range, u32>,
         >
         PartialSpell<
             'a,
@@ -505,7 +1295,7 @@
         >
     {
         #[inline(always)]
-        pub fn effects<effects: ::partial_init_core::Init<__Spell__::effects, Vec<&'a E>>>(
+        pub fn effects<effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>>(
             self,
             effects: effects,
         ) -> PartialSpell<'a, E, with_name, damage, range, effects> {
@@ -522,10 +1312,10 @@
     impl<
             'a,
             E: Effect + ?Sized,
-            with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-            damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-            range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-            effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+            with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+            damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+            range: ::partial_init_core::FieldState<__Spell__::range, u32>,
+            effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
         > PartialSpell<'a, E, with_name, damage, range, effects>
     {
         #[inline(always)]
@@ -626,10 +1416,10 @@
     #[allow(non_camel_case_types)]
     pub struct PartialSpell<
         'a, E: Effect + ?Sized,
-        with_name: ::partial_init_core::MaybeInit<__Spell__::with_name, String>,
-        damage: ::partial_init_core::MaybeInit<__Spell__::damage, u32>,
-        range: ::partial_init_core::MaybeInit<__Spell__::range, u32>,
-        effects: ::partial_init_core::MaybeInit<__Spell__::effects, Vec<&'a E>>,
+        with_name: ::partial_init_core::FieldState<__Spell__::with_name, String>,
+        damage: ::partial_init_core::FieldState<__Spell__::damage, u32>,
+        range: ::partial_init_core::FieldState<__Spell__::range, u32>,
+        effects: ::partial_init_core::FieldState<__Spell__::effects, Vec<&'a E>>,
     > {
         with_name: with_name,
         damage: damage,
@@ -642,7 +1432,7 @@
     This is a very generic struct. Each of the fields of `Spell` is a field on 
     `PartialSpell` with it's own generic type. There is also a `PhantomData` field, 
     that is there so that I can use the generic type on `Spell` everywhere I use 
-    `ParitalSpell`, and so that I can add the `::partial_init_core::MaybeInit<_, _>` 
+    `ParitalSpell`, and so that I can add the `::partial_init_core::FieldState<_, _>` 
     bound on the generic types. This bounds enforces the author to be responisble when 
     using `PartialSpell`, meaning it can always be built (not precisely true, but good 
     enough).
@@ -808,116 +1598,609 @@
         WithOption {
             value: 10
         }
-    };
-    ```
-*/
+    };
+    ```
+*/
+
+extern crate proc_macro;
+extern crate proc_macro2;
+
+#[macro_use]
+extern crate syn;
+
+#[macro_use]
+extern crate quote;
+
+use self::proc_macro::TokenStream;
+use syn::parse::Error;
+use syn::{punctuated::Punctuated, token::Comma, Ident};
+
+mod dynamic;
+#[cfg(feature = "serde")]
+mod deserialize;
+
+// #[default], #[default(<path>)], #[default = <expr>], #[default(expr = <expr>)]
+// or #[default(with = <path>, depends_on(<field>, ..))]
+enum DefaultAttribute {
+    Path(syn::Path),
+    Expr(proc_macro2::TokenStream),
+    // the span is used to report cycles in `depends_on`
+    With(syn::Path, List<Ident>, proc_macro2::Span),
+    Default,
+    None,
+}
+
+impl DefaultAttribute {
+    fn is_none(&self) -> bool {
+        matches!(self, DefaultAttribute::None)
+    }
+
+    // unwraps `value`, the `Option` of the field, with the default value,
+    // `deps` are the locals that hold the fields listed in `depends_on`
+    fn unwrap_value(&self, value: proc_macro2::TokenStream, deps: &[&Ident]) -> proc_macro2::TokenStream {
+        match self {
+            DefaultAttribute::Path(def) => quote! { #value.unwrap_or_else(#def) },
+            DefaultAttribute::Expr(expr) => quote! { #value.unwrap_or_else(|| #expr) },
+            DefaultAttribute::With(def, _, _) => {
+                let deps = deps.iter();
+                quote! { #value.unwrap_or_else(|| #def(#(&#deps),*)) }
+            }
+            DefaultAttribute::Default => quote! { #value.unwrap_or_default() },
+            DefaultAttribute::None => value,
+        }
+    }
+}
+
+// Attribute handler
+struct PartialInitAttribute {
+    fun: Option<Ident>,
+    def: DefaultAttribute,
+    deinit: bool,
+    into: bool,
+    strip_option: bool,
+    nested: bool,
+    each: Option<Ident>,
+    validate: Vec<syn::Path>,
+    vis: Option<syn::Visibility>,
+    // the value of a #[skip] field
+    skip: Option<proc_macro2::TokenStream>,
+}
+
+impl PartialInitAttribute {
+    fn new() -> Self {
+        Self {
+            fun: None,
+            def: DefaultAttribute::None,
+            deinit: false,
+            into: false,
+            strip_option: false,
+            nested: false,
+            each: None,
+            validate: Vec::new(),
+            vis: None,
+            skip: None,
+        }
+    }
+}
+
+impl Default for PartialInitAttribute {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum AttrParseError {
+    NotMine,
+    Error(syn::parse::Error),
+}
+
+// #[deinit], #[into] and #[partial_init(..)] on a struct, enum, or enum variant
+#[derive(Clone, Default)]
+struct TypeAttribute {
+    deinit: bool,
+    into: bool,
+    dynamic: bool,
+    validate: Option<syn::Path>,
+    prefix: Option<syn::LitStr>,
+    rename_all: Option<RenameRule>,
+    partial: Option<Ident>,
+    module: Option<Ident>,
+    build_fn: Option<Ident>,
+    deinit_prefix: Option<syn::LitStr>,
+    vis: Option<syn::Visibility>,
+}
+
+// the names of the final steps of the builder api, these are based on
+// `build`, which can be changed with #[partial_init(build_fn = name)]
+struct BuildFns {
+    build: Ident,
+    try_build: Ident,
+    try_build_fallible: Ident,
+    build_async: Ident,
+}
+
+// the names of the generated types, and of the type that is built
+#[derive(Clone, Copy)]
+struct TypeNames<'a> {
+    vis: &'a syn::Visibility,
+    name: &'a Ident,
+    target_doc: &'a str,
+    partial_name: &'a Ident,
+    internal_module: &'a Ident,
+    dynamic_name: &'a Ident,
+}
+
+// the fields that are in the `Partial*` type, i.e. not #[skip]
+#[derive(Clone, Copy)]
+struct FieldList<'a> {
+    field_names: &'a List<Ident>,
+    fields: &'a List<syn::Field>,
+    field_vis: &'a [syn::Visibility],
+    hidden: &'a [bool],
+    attrs: &'a [PartialInitAttribute],
+}
+
+// the generics of the type, and the generics of the `Partial*` type
+#[derive(Clone, Copy)]
+struct GenericList<'a> {
+    ast_generics: &'a syn::Generics,
+    lifetimes: &'a List<&'a syn::Lifetime>,
+    generics: &'a List<&'a Ident>,
+    generic_decl: &'a proc_macro2::TokenStream,
+    generic_args: &'a proc_macro2::TokenStream,
+}
+
+impl TypeAttribute {
+    // the FDFs are named `{deinit_prefix}{func_name}`
+    fn deinit_prefix(&self) -> String {
+        self.deinit_prefix.as_ref().map_or_else(|| "deinit_".to_owned(), syn::LitStr::value)
+    }
+}
+
+impl BuildFns {
+    fn new(type_attr: &TypeAttribute, span: proc_macro2::Span) -> Self {
+        let build = type_attr.build_fn.clone().unwrap_or_else(|| Ident::new("build", span));
+
+        BuildFns {
+            try_build: derived_ident("try_", &build, ""),
+            try_build_fallible: derived_ident("try_", &build, "_fallible"),
+            build_async: derived_ident("", &build, "_async"),
+            build,
+        }
+    }
+}
+
+// #[partial_init(rename_all = "<rule>")], the names of the FIFs are created
+// by applying the rule to the name of the field, and then adding the prefix
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Snake,
+    Camel,
+    Pascal,
+    ScreamingSnake,
+    Lower,
+    Upper,
+}
+
+impl RenameRule {
+    fn parse(rule: &syn::LitStr) -> Result<Self, syn::parse::Error> {
+        match &*rule.value() {
+            "snake_case" => Ok(RenameRule::Snake),
+            "camelCase" => Ok(RenameRule::Camel),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            _ => Err(Error::new(
+                rule.span(),
+                "unknown `rename_all` rule, expected one of \"snake_case\", \"camelCase\", \"PascalCase\", \"SCREAMING_SNAKE_CASE\", \"lowercase\" or \"UPPERCASE\"",
+            )),
+        }
+    }
+
+    // `name` is the name of a field, which is usually in snake case,
+    // leading underscores are kept so that `_0` stays a valid identifier
+    fn apply(self, name: &str) -> String {
+        let trimmed = name.trim_start_matches('_');
+        let (leading, name) = name.split_at(name.len() - trimmed.len());
+
+        let words = name.split('_').filter(|word| !word.is_empty());
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars.next()
+                .map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+                .unwrap_or_default()
+        };
+
+        let renamed = match self {
+            RenameRule::Snake => to_snake_case(name),
+            RenameRule::ScreamingSnake => to_snake_case(name).to_uppercase(),
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => words.map(capitalize).collect(),
+            RenameRule::Camel => words.enumerate()
+                .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect(),
+        };
+
+        format!("{}{}", leading, renamed)
+    }
+}
+
+// the arguments of #[default = <expr>], #[default(expr = <expr>)] and
+// #[default(with = <path>, depends_on(<field>, ..))], the expression is kept
+// as tokens so that it is only checked once it is put in `build`
+struct DefaultArgs(Option<DefaultAttribute>);
+
+impl syn::parse::Parse for DefaultArgs {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, syn::parse::Error> {
+        if input.peek(Token![=]) {
+            let eq = input.parse::<Token![=]>()?;
+            return DefaultArgs::expr(eq, input.parse()?);
+        }
+
+        if !input.peek(syn::token::Paren) {
+            input.parse::<proc_macro2::TokenStream>()?;
+            return Ok(DefaultArgs(None));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        if !(content.peek(Ident) && (content.peek2(Token![=]) || content.peek2(syn::token::Paren))) {
+            // #[default(<path>)]
+            content.parse::<proc_macro2::TokenStream>()?;
+            return Ok(DefaultArgs(None));
+        }
+
+        let mut with = None;
+        let mut depends_on = None;
+
+        loop {
+            let name: Ident = content.parse()?;
+
+            if name == "expr" {
+                if with.is_some() || depends_on.is_some() {
+                    return Err(Error::new(name.span(), "`expr` can't be used with `with` or `depends_on`"));
+                }
+
+                let eq = content.parse::<Token![=]>()?;
+                return DefaultArgs::expr(eq, content.parse()?);
+            } else if name == "with" && with.is_none() {
+                content.parse::<Token![=]>()?;
+                with = Some((name, content.parse::<syn::Path>()?));
+            } else if name == "depends_on" && depends_on.is_none() {
+                let list;
+                parenthesized!(list in content);
+                let fields: List<Ident> = list.parse_terminated(<Ident as syn::parse::Parse>::parse)?;
+                depends_on = Some((name, fields));
+            } else if name == "with" || name == "depends_on" {
+                return Err(Error::new(name.span(), format!("duplicate `{}`", name)));
+            } else {
+                return Err(Error::new(name.span(), "unknown `default` argument"));
+            }
+
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+            if content.is_empty() {
+                break;
+            }
+        }
 
-extern crate proc_macro;
-extern crate proc_macro2;
+        match (with, depends_on) {
+            (Some((_, path)), Some((name, fields))) => Ok(DefaultArgs(Some(DefaultAttribute::With(path, fields, name.span())))),
+            (Some((name, path)), None) => Ok(DefaultArgs(Some(DefaultAttribute::With(path, List::new(), name.span())))),
+            (None, Some((name, _))) => Err(Error::new(name.span(), "`depends_on` can only be used with `with`")),
+            (None, None) => unreachable!(),
+        }
+    }
+}
 
-#[macro_use]
-extern crate syn;
+impl DefaultArgs {
+    fn expr(eq: Token![=], expr: proc_macro2::TokenStream) -> Result<Self, syn::parse::Error> {
+        if expr.is_empty() {
+            return Err(Error::new(eq.spans[0], "expected an expression"));
+        }
 
-#[macro_use]
-extern crate quote;
+        Ok(DefaultArgs(Some(DefaultAttribute::Expr(expr))))
+    }
+}
 
-use self::proc_macro::TokenStream;
-use syn::parse::Error;
-use syn::{punctuated::Punctuated, token::Comma, Ident};
+// #[each = "<name>"], rustc only allows literals after `=` in attributes,
+// so the name is a string, #[each(<name>)] is handled like #[func_name(<name>)]
+struct EachArgs(Option<Ident>);
 
-// #[default] or #[default(<path>)]
-enum DefaultAttribute {
+impl syn::parse::Parse for EachArgs {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, syn::parse::Error> {
+        if !input.peek(Token![=]) {
+            input.parse::<proc_macro2::TokenStream>()?;
+            return Ok(EachArgs(None));
+        }
+
+        input.parse::<Token![=]>()?;
+        let name = input.parse::<syn::LitStr>()?;
+        Ok(EachArgs(Some(name.parse()?)))
+    }
+}
+
+// #[partial_init(<name>, <name> = <value>, ..)]
+struct PartialInitArgs(List<PartialInitArg>);
+
+struct PartialInitArg {
+    name: Ident,
+    value: Option<PartialInitValue>,
+}
+
+enum PartialInitValue {
+    Lit(syn::Lit),
     Path(syn::Path),
-    Default,
-    None,
 }
 
-// Attribute handler
-struct PartialInitAttribute {
-    fun: Option<Ident>,
-    def: DefaultAttribute,
-    deinit: bool
+impl syn::parse::Parse for PartialInitArgs {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, syn::parse::Error> {
+        let content;
+        parenthesized!(content in input);
+        Ok(PartialInitArgs(content.parse_terminated(PartialInitArg::parse)?))
+    }
 }
 
-impl PartialInitAttribute {
-    fn new() -> Self {
-        Self {
-            fun: None,
-            def: DefaultAttribute::None,
-            deinit: false
-        }
+impl syn::parse::Parse for PartialInitArg {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, syn::parse::Error> {
+        let name = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(syn::Lit) {
+                Some(PartialInitValue::Lit(input.parse()?))
+            } else {
+                Some(PartialInitValue::Path(input.parse()?))
+            }
+        } else {
+            None
+        };
+
+        Ok(PartialInitArg { name, value })
     }
 }
 
-impl Default for PartialInitAttribute {
-    fn default() -> Self {
-        Self::new()
+impl PartialInitArg {
+    // for arguments that don't take a value, like `dynamic`
+    fn flag(&self) -> Result<bool, syn::parse::Error> {
+        match &self.value {
+            Some(value) => Err(Error::new(value.span(), format!("`{}` takes no value", self.name))),
+            None => Ok(true),
+        }
+    }
+
+    // for arguments that take a string, like `prefix = "with_"`
+    fn string(&self) -> Result<syn::LitStr, syn::parse::Error> {
+        match &self.value {
+            Some(PartialInitValue::Lit(syn::Lit::Str(lit))) => Ok(lit.clone()),
+            Some(value) => Err(Error::new(value.span(), format!("`{}` takes a string", self.name))),
+            None => Err(Error::new(self.name.span(), format!("`{}` takes a string", self.name))),
+        }
+    }
+
+    // for arguments that take a path, like `validate = path`
+    fn path(&self) -> Result<syn::Path, syn::parse::Error> {
+        match &self.value {
+            Some(PartialInitValue::Path(path)) => Ok(path.clone()),
+            Some(value) => Err(Error::new(value.span(), format!("`{}` takes a path", self.name))),
+            None => Err(Error::new(self.name.span(), format!("`{}` takes a path", self.name))),
+        }
+    }
+
+    // for arguments that take a visibility in a string, like `vis = "pub(crate)"`,
+    // an empty string means private
+    fn visibility(&self) -> Result<syn::Visibility, syn::parse::Error> {
+        let lit = self.string()?;
+
+        syn::parse_str(&lit.value())
+            .map_err(|_| Error::new(lit.span(), format!("`{}` is not a visibility", lit.value())))
+    }
+
+    // for arguments that take a single identifier, like `build_fn = finish`
+    fn ident(&self) -> Result<Ident, syn::parse::Error> {
+        match &self.value {
+            Some(PartialInitValue::Path(path))
+                if path.leading_colon.is_none()
+                    && path.segments.len() == 1
+                    && path.segments[0].arguments.is_empty() =>
+            {
+                Ok(path.segments[0].ident.clone())
+            }
+            Some(value) => Err(Error::new(value.span(), format!("`{}` takes an identifier", self.name))),
+            None => Err(Error::new(self.name.span(), format!("`{}` takes an identifier", self.name))),
+        }
     }
 }
 
-enum AttrParseError {
-    NotMine,
-    Error(syn::parse::Error),
+impl PartialInitValue {
+    fn span(&self) -> proc_macro2::Span {
+        use syn::spanned::Spanned;
+
+        match self {
+            PartialInitValue::Lit(lit) => lit.span(),
+            PartialInitValue::Path(path) => path.span(),
+        }
+    }
 }
 
 fn quote_one<T: quote::ToTokens>(t: T) -> proc_macro2::TokenStream {
     quote! { #t, }
 }
 
+// the type and const parameters of `generics`, in the order they were declared,
+// these go after the lifetimes in every generic parameter list we create
+fn type_and_const_params(generics: &syn::Generics) -> impl Iterator<Item = &syn::GenericParam> {
+    generics.params.iter().filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+}
+
+// a generic parameter for an impl or the `Partial*` type, without it's default,
+// because defaults are only allowed on the type, and only at the end
+fn quote_param(param: &syn::GenericParam) -> proc_macro2::TokenStream {
+    let mut param = param.clone();
+    match &mut param {
+        syn::GenericParam::Type(ty) => {
+            ty.eq_token = None;
+            ty.default = None;
+        }
+        syn::GenericParam::Const(c) => {
+            c.eq_token = None;
+            c.default = None;
+        }
+        syn::GenericParam::Lifetime(_) => (),
+    }
+    quote! { #param, }
+}
+
 fn to_string<T: quote::ToTokens>(t: T) -> String {
     format! { "{}" , quote! { #t } }
 }
 
 type List<T> = Punctuated<T, Comma>;
 
+// must be kept in sync with `partial_init_core::MissingFields::MAX_FIELDS`
+const MAX_REQUIRED_FIELDS: usize = 128;
+
 #[doc(hidden)]
-#[proc_macro_derive(PartialInit, attributes(deinit, func_name, default))]
+#[proc_macro_derive(PartialInit, attributes(deinit, into, strip_option, nested, each, skip, func_name, default, validate, partial_init))]
 pub fn partial_init_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::DeriveInput);
 
-    let name = ast.ident;
+    let name = &ast.ident;
+    let type_attr = match parse_type_attributes(&ast.attrs, &TypeAttribute::default()) {
+        Ok(type_attr) => type_attr,
+        Err(msg) => return msg.to_compile_error().into(),
+    };
 
-    let fields: List<_> = {
-        const ERROR_INFO: &str = "only structs with named fields are allowed to be used in partial initialization";
-        if let syn::Data::Struct(data) = ast.data {
-            if let syn::Fields::Named(fields) = data.fields {
-                fields.named
-            } else {
-                return Error::new(name.span(), ERROR_INFO).to_compile_error().into();
+    const ERROR_INFO: &str = "only structs with named or unnamed fields and enums are allowed to be used in partial initialization";
+    let expanded = match &ast.data {
+        syn::Data::Struct(data) => {
+            let fields = match &data.fields {
+                syn::Fields::Named(fields) => &fields.named,
+                syn::Fields::Unnamed(fields) => &fields.unnamed,
+                syn::Fields::Unit => return Error::new(name.span(), ERROR_INFO).to_compile_error().into(),
+            };
+
+            partial_init_impl(&type_attr, &ast.vis, name, None, fields, &ast.generics)
+        }
+        syn::Data::Enum(data) => {
+            let mut expanded = quote! {};
+
+            // every variant gets its own `Partial*` type and `__*__` module
+            if let Some(arg) = type_attr.partial.as_ref().or(type_attr.module.as_ref()) {
+                return Error::new(arg.span(), "the names of the generated types must be given on each variant of an enum")
+                    .to_compile_error()
+                    .into();
             }
-        } else {
-            return Error::new(name.span(), ERROR_INFO).to_compile_error().into();
+
+            // unit variants don't have anything to initialize, so they are skipped
+            for variant in data.variants.iter() {
+                let fields = match &variant.fields {
+                    syn::Fields::Named(fields) => &fields.named,
+                    syn::Fields::Unnamed(fields) => &fields.unnamed,
+                    syn::Fields::Unit => continue,
+                };
+
+                let variant_impl = parse_type_attributes(&variant.attrs, &type_attr)
+                    .and_then(|type_attr| partial_init_impl(&type_attr, &ast.vis, name, Some(&variant.ident), fields, &ast.generics));
+
+                match variant_impl {
+                    Ok(variant_impl) => expanded.extend(variant_impl),
+                    Err(msg) => return msg.to_compile_error().into(),
+                }
+            }
+
+            Ok(expanded)
         }
+        syn::Data::Union(_) => return Error::new(name.span(), ERROR_INFO).to_compile_error().into(),
     };
 
-    let impl_deinit = {
-        let mut impl_deinit = false;
+    match expanded {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(msg) => msg.to_compile_error().into(),
+    }
+}
 
-        for i in ast.attrs {
-            if to_string(i.path) == "deinit" {
-                impl_deinit = true;
-                break;
+// `inherited` holds the attributes of the enum when parsing the attributes of a variant
+fn parse_type_attributes(attrs: &[syn::Attribute], inherited: &TypeAttribute) -> Result<TypeAttribute, syn::parse::Error> {
+    let mut type_attr = inherited.clone();
+
+    for attr in attrs {
+        match &*to_string(&attr.path) {
+            "deinit" => type_attr.deinit = true,
+            "into" => type_attr.into = true,
+            "partial_init" => {
+                let PartialInitArgs(args) = syn::parse2(attr.tts.clone())?;
+
+                for arg in args.iter() {
+                    match &*arg.name.to_string() {
+                        "dynamic" => type_attr.dynamic = arg.flag()?,
+                        "validate" => type_attr.validate = Some(arg.path()?),
+                        "prefix" => type_attr.prefix = Some(arg.string()?),
+                        "rename_all" => type_attr.rename_all = Some(RenameRule::parse(&arg.string()?)?),
+                        "partial" => type_attr.partial = Some(arg.ident()?),
+                        "module" => type_attr.module = Some(arg.ident()?),
+                        "build_fn" => type_attr.build_fn = Some(arg.ident()?),
+                        "deinit_prefix" => type_attr.deinit_prefix = Some(arg.string()?),
+                        "vis" => type_attr.vis = Some(arg.visibility()?),
+                        _ => return Err(Error::new(arg.name.span(), "unknown `partial_init` argument")),
+                    }
+                }
             }
+            _ => (),
         }
+    }
 
-        impl_deinit
-    };
-    
-    let vis = ast.vis;
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(type_attr)
+}
 
-    let (partial_name, internal_module) = {
-        let ast_name = &name;
-        let name = quote! { #ast_name };
+// Creates the `Partial*` type and all of it's impls for either a struct,
+// or a single variant of an enum
+fn partial_init_impl(
+    type_attr: &TypeAttribute,
+    vis: &syn::Visibility,
+    name: &Ident,
+    variant: Option<&Ident>,
+    fields: &List<syn::Field>,
+    ast_generics: &syn::Generics,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let (impl_generics, ty_generics, where_clause) = ast_generics.split_for_impl();
+
+    // `Partial{name}{variant}`, `__{name}{variant}__`, and `Dyn{name}{variant}Builder`,
+    // the first two can be changed with #[partial_init(partial = name, module = name)]
+    let (partial_name, internal_module, dynamic_name) = {
+        let full_name = match variant {
+            Some(variant) => derived_name("", name, &derived_name("", variant, "")),
+            None => derived_name("", name, ""),
+        };
         (
-            Ident::new(&*format!("Partial{}", name), ast_name.span()),
-            Ident::new(&*format!("__{}__", name), ast_name.span()),
+            type_attr.partial.clone().unwrap_or_else(|| Ident::new(&format!("Partial{}", full_name), name.span())),
+            type_attr.module.clone().unwrap_or_else(|| Ident::new(&format!("__{}__", full_name), name.span())),
+            Ident::new(&format!("Dyn{}Builder", full_name), name.span()),
         )
     };
 
+    check_type_names(type_attr, name, &partial_name, &internal_module, &dynamic_name)?;
+
+    let build_fns = BuildFns::new(type_attr, name.span());
+    let BuildFns { build, try_build, try_build_fallible, build_async } = &build_fns;
+
+    // structs get `PartialInit::uninit`, enum variants get `{name}::uninit_{variant}`
+    let uninit_fn = match variant {
+        Some(variant) => Ident::new(&format!("uninit_{}", to_snake_case(&derived_name("", variant, ""))), variant.span()),
+        None => Ident::new("uninit", name.span()),
+    };
+
+    // what gets built, and how it is refered to in the docs
+    let (target, target_doc) = match variant {
+        Some(variant) => (quote! { #name::#variant }, format!("{}::{}", name, variant)),
+        None => (quote! { #name }, format!("{}", name)),
+    };
+
     let lifetimes = {
-        let mut lt: List<_> = ast
-            .generics
+        let mut lt: List<_> = ast_generics
             .lifetimes()
             .map(|x| &x.lifetime)
             .collect();
@@ -928,46 +2211,163 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
     };
 
     let generics = {
-        let mut gen: List<_> = ast.generics.type_params().map(|x| &x.ident).collect();
+        let mut gen: List<_> = type_and_const_params(ast_generics)
+            .map(|x| match x {
+                syn::GenericParam::Type(ty) => &ty.ident,
+                syn::GenericParam::Const(c) => &c.ident,
+                syn::GenericParam::Lifetime(_) => unreachable!(),
+            })
+            .collect();
         if !gen.is_empty() {
             gen.push_punct(Token![,](name.span()));
         }
         gen
     };
 
-    let attrs: Vec<_> = match parse_attributes(&fields) {
-        Ok(attrs) => attrs,
-        Err(msg) => return msg.to_compile_error().into(),
-    };
-    
-    // before #[func_name] is applied
+    let attrs = parse_attributes(fields)?;
+
+    // before #[func_name] is applied,
+    // tuple structs use the field index
     let old_field_names: List<_> = fields
         .iter()
-        .map(|field| field.ident.as_ref().unwrap())
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index { index: i as u32, span: name.span() }),
+        })
+        .collect();
+
+    // #[skip] fields don't get a generic parameter, a marker type or any functions,
+    // so they are split off here, and only come back when the value is built
+    let all_fields = fields;
+    let mut skipped = quote! {};
+    // `FieldName::INDEX` is still the position in the declaration, so it is kept as well
+    let (fields, attrs, old_field_names, field_index) = {
+        let mut kept = (List::new(), Vec::new(), List::new(), Vec::new());
+
+        for (index, ((field, attr), old_field)) in fields.iter().zip(attrs).zip(old_field_names).enumerate() {
+            match &attr.skip {
+                Some(value) => skipped.extend(quote! { #old_field: #value, }),
+                None => {
+                    kept.0.push(field.clone());
+                    kept.1.push(attr);
+                    kept.2.push(old_field);
+                    kept.3.push(index);
+                }
+            }
+        }
+
+        kept
+    };
+    let fields = &fields;
+
+    let (order, deps) = evaluation_order(&old_field_names, &attrs)?;
+
+    // the visibility of the FIFs and FDFs of each field, this is the visibility of the field
+    // unless it's set with #[partial_init(vis = "..")], but never more than the type
+    let field_vis: Vec<syn::Visibility> = fields
+        .iter()
+        .zip(attrs.iter())
+        .map(|(field, attr)| match (&attr.vis, &type_attr.vis) {
+            (Some(field_vis), _) | (None, Some(field_vis)) if vis_rank(field_vis) > vis_rank(vis) => vis.clone(),
+            (Some(field_vis), _) | (None, Some(field_vis)) => field_vis.clone(),
+            // the fields of an enum variant are always as visible as the enum
+            (None, None) if variant.is_some() => vis.clone(),
+            (None, None) if vis_rank(&field.vis) < vis_rank(vis) => field.vis.clone(),
+            (None, None) => vis.clone(),
+        })
         .collect();
 
-    // after #[func_name] is applied
+    // fields that can't be initialized everywhere that `build` can be called
+    let hidden: Vec<bool> = field_vis.iter().map(|field_vis| vis_rank(field_vis) < vis_rank(vis)).collect();
+
+    for ((field, attr), &hidden) in fields.iter().zip(attrs.iter()).zip(hidden.iter()) {
+        use syn::spanned::Spanned;
+
+        if let (true, DefaultAttribute::None) = (hidden, &attr.def) {
+            return Err(Error::new(
+                field.span(),
+                format!(
+                    "this field is less visible than `{}`, so it must have a `#[default]`, \
+                     or `#[partial_init(vis = \"..\")]` to make its FIFs more visible",
+                    target_doc,
+                ),
+            ));
+        }
+    }
+
+    // after #[func_name] is applied,
+    // tuple structs default to `_{index}`
+    // and #[partial_init(prefix = "..", rename_all = "..")] is applied
+    // to the fields without #[func_name]
     let field_names: List<_> = old_field_names
         .iter()
         .zip(attrs.iter())
-        .map(|(old_field, attr)| attr.fun.as_ref().unwrap_or(old_field))
+        .map(|(old_field, attr)| {
+            let field = match (&attr.fun, old_field) {
+                (Some(fun), _) => return Ok(fun.clone()),
+                (None, syn::Member::Named(ident)) => ident.clone(),
+                (None, syn::Member::Unnamed(index)) => Ident::new(&format!("_{}", index.index), index.span),
+            };
+
+            if type_attr.prefix.is_none() && type_attr.rename_all.is_none() {
+                return Ok(field);
+            }
+
+            let name = field.to_string();
+            let name = name.trim_start_matches("r#");
+            let name = match type_attr.rename_all {
+                Some(rule) => rule.apply(name),
+                None => name.to_owned(),
+            };
+            let name = match &type_attr.prefix {
+                Some(prefix) => format!("{}{}", prefix.value(), name),
+                None => name,
+            };
+
+            syn::parse_str::<Ident>(&name)
+                .map(|ident| Ident::new(&ident.to_string(), field.span()))
+                .map_err(|_| Error::new(field.span(), format!("`{}` is not a valid function name", name)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    check_function_names(type_attr, &field_names, &attrs, ast_generics)?;
+
+    // `FieldName::NAME` is the name that the field was declared with, so #[func_name],
+    // `prefix` and `rename_all` only change the functions, and tuple structs use `_{index}`
+    let declared_names: Vec<_> = old_field_names
+        .iter()
+        .map(|old_field| match old_field {
+            syn::Member::Named(ident) => derived_name("", ident, ""),
+            syn::Member::Unnamed(index) => format!("_{}", index.index),
+        })
         .collect();
     
     // stuff to throw in the PhantomData, so we can have nice generics
-    let phantom_data: List<_> = fields.iter().map(|field| &field.ty).collect();
-    // fields in the struct declaration
-    let struct_fields: List<_> = field_names
+    let phantom_data: List<_> = {
+        let mut phantom_data: List<_> = all_fields.iter().map(|field| { let ty = &field.ty; quote! { #ty } }).collect();
+
+        // enum variants don't have to use all of the generic parameters of the enum
+        if variant.is_some() {
+            phantom_data.push(quote! { #name #ty_generics });
+        }
+
+        phantom_data
+    };
+    // fields in the struct declaration, each one ends in a comma, so that there is no stray
+    // comma in front of the `PhantomData` when every field is `#[skip]`
+    let struct_fields: proc_macro2::TokenStream = field_names
         .iter()
-        .map(|field| quote! { #field: #field })
+        .map(|field| quote! { #field: #field, })
         .collect();
 
     // Generic arguements without bounds
     let generic_args = quote! { #lifetimes #generics #field_names };
-    // Generic arguements with bounds
+    // Generic arguements with bounds, the fields can be read
     let generic_decl = {
         let mut temp = quote! {};
-        temp.extend(ast.generics.lifetimes().map(quote_one));
-        temp.extend(ast.generics.type_params().map(quote_one));
+        temp.extend(ast_generics.lifetimes().map(quote_one));
+        temp.extend(type_and_const_params(ast_generics).map(quote_param));
         temp.extend(
             field_names
                 .iter()
@@ -978,61 +2378,333 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
         );
         temp
     };
+    // Generic arguements with bounds, the fields may only be readable by `build_async`
+    let state_decl = {
+        let mut temp = quote! {};
+        temp.extend(ast_generics.lifetimes().map(quote_one));
+        temp.extend(type_and_const_params(ast_generics).map(quote_param));
+        temp.extend(
+            field_names
+                .iter()
+                .zip(fields.iter().map(|x| &x.ty))
+                .map(|(x, ty)| {
+                    quote! { #x: ::partial_init_core::FieldState<#internal_module::#x, #ty>, }
+                }),
+        );
+        temp
+    };
+
+    // Stuff that goes on in the uninit function
+    let (uninit_struct, uninit_struct_initializer) = {
+        let mut uninit_struct = quote! { #lifetimes #generics };
+        uninit_struct.extend(field_names.iter().zip(fields.iter().map(|x| &x.ty)).map(
+            |(x, ty)| {
+                quote! { ::partial_init_core::Uninit<#internal_module::#x, #ty>, }
+            },
+        ));
+
+        let mut initializer = quote! { __phantom_data__partial_init_: Default::default() };
+        initializer.extend(field_names.iter().map(|x| quote! { ,#x: Default::default() }));
+        (uninit_struct, initializer)
+    };
+
+    // the locals that hold the fields in the build functions, these don't use the names of
+    // the fields, so that they can't shadow the paths in #[default], #[validate] and #[skip]
+    let locals: List<_> = old_field_names
+        .iter()
+        .map(|old_field| match old_field {
+            syn::Member::Named(ident) => derived_ident("__partial_init_", ident, ""),
+            syn::Member::Unnamed(index) => Ident::new(&format!("__partial_init_{}", index.index), index.span),
+        })
+        .collect();
+    let locals = &locals;
+
+    // Stuff that builds the value from the locals made by `build` and `try_build`, and runs
+    // the validators on it, `wrap` converts a `ValidationError` into the error of the function
+    let has_validators = type_attr.validate.is_some() || attrs.iter().any(|attr| !attr.validate.is_empty());
+    let validated = |wrap: proc_macro2::TokenStream| {
+        let mut initializer = quote! {};
+        initializer.extend(locals.iter().zip(old_field_names.iter()).map(|(local, old_field)| {
+            quote! { #old_field: #local, }
+        }));
+        initializer.extend(skipped.clone());
+
+        if !has_validators {
+            return quote! { #target { #initializer } };
+        }
+
+        let wrap = &wrap;
+        let internal_module = &internal_module;
+        let mut checks = quote! {};
+        checks.extend(field_names.iter().zip(locals.iter()).zip(attrs.iter()).flat_map(|((field, local), attr)| {
+            attr.validate.iter().map(move |validate| quote! {
+                if let Err(message) = #validate(&#local) {
+                    return Err(#wrap(::partial_init_core::ValidationError::for_field::<#internal_module::#field>(message)));
+                }
+            })
+        }));
+
+        // `build` returns the `ValidationError` as it is, so it can use `?` directly
+        let type_check = type_attr.validate.as_ref().map(|validate| {
+            if wrap.is_empty() {
+                quote! { #validate(&value)?; }
+            } else {
+                quote! { #validate(&value).map_err(#wrap)?; }
+            }
+        });
+
+        quote! {
+            #checks
+            let value = #target { #initializer };
+            #type_check
+            value
+        }
+    };
+
+    // Stuff that fills in the defaults, once the fields are in locals holding
+    // `Option`s, this is shared by `try_build` and `build_async`
+    let defaults = {
+        let mut defaults = quote! {};
+        defaults.extend(order.iter().filter(|&&i| !attrs[i].def.is_none()).map(|&i| {
+            let local = &locals[i];
+            let local_deps: Vec<_> = deps[i].iter().map(|&j| &locals[j]).collect();
+            let value = attrs[i].def.unwrap_value(quote! { #local }, &local_deps);
+
+            quote! { let #local = #value; }
+        }));
+        defaults
+    };
+
+    // Stuff that goes on in the build function
+    let (init_struct, init_struct_initializer) = {
+        let mut init_struct = quote! {};
+        init_struct.extend(ast_generics.lifetimes().map(quote_one));
+        init_struct.extend(type_and_const_params(ast_generics).map(quote_param));
+        init_struct.extend(
+            field_names
+                .iter()
+                .zip(fields.iter().map(|field| &field.ty))
+                .zip(attrs.iter())
+                .map(|((field, ty), attr)| {
+                    if let DefaultAttribute::None = &attr.def {
+                        quote! { #field: ::partial_init_core::Init<#internal_module::#field, #ty>, }
+                    } else {
+                        quote! { #field: ::partial_init_core::MaybeInit<#internal_module::#field, #ty>, }
+                    }
+                }),
+        );
+
+        // the fields are evaluated in order, so that `depends_on` can refer to them
+        let mut values = quote! {};
+        values.extend(order.iter().map(|&i| {
+            let field = &field_names[i];
+            let local = &locals[i];
+            let local_deps: Vec<_> = deps[i].iter().map(|&j| &locals[j]).collect();
+
+            if let DefaultAttribute::None = &attrs[i].def {
+                quote! { let #local = ::partial_init_core::Init::get(self.#field); }
+            } else {
+                let value = attrs[i].def.unwrap_value(
+                    quote! { ::partial_init_core::MaybeInit::get(self.#field) },
+                    &local_deps,
+                );
+                quote! { let #local = #value; }
+            }
+        }));
+
+        let construct = validated(quote! {});
+        let initializer = if has_validators {
+            quote! {
+                #values
+                Ok({ #construct })
+            }
+        } else {
+            quote! {
+                #values
+                #construct
+            }
+        };
+
+        (init_struct, initializer)
+    };
+
+    // Stuff that goes on in the try_build and try_build_fallible functions, they only
+    // differ in how the fields are read, and the type of the error.
+    // `MissingFields` can only track so many fields, so if there are more required fields
+    // than that, both functions get a bound that is never met, so calling them is an error
+    let (try_build_body, try_build_fallible_body, too_many_required) = {
+        let (required, required_locals): (List<_>, List<_>) = field_names
+            .iter()
+            .zip(locals.iter())
+            .zip(attrs.iter())
+            .filter(|(_, attr)| attr.def.is_none())
+            .map(|(field_local, _)| field_local)
+            .unzip();
+
+        let body = |values: proc_macro2::TokenStream, missing_err: proc_macro2::TokenStream, invalid: proc_macro2::TokenStream| {
+            let construct = validated(invalid);
+            let initializer = quote! {
+                #defaults
+                #construct
+            };
+
+            if required.is_empty() {
+                quote! {
+                    #values
+                    Ok({ #initializer })
+                }
+            } else {
+                let required_names: List<_> = required
+                    .iter()
+                    .map(|field| quote! { <#internal_module::#field as ::partial_init_core::FieldName>::NAME })
+                    .collect();
+                let required_values: List<_> = required_locals.iter().map(|local| quote! { Some(#local) }).collect();
+                let mut missing = quote! {};
+                missing.extend(required_locals.iter().enumerate().map(|(i, local)| quote! {
+                    if #local.is_none() {
+                        missing.insert(#i);
+                    }
+                }));
+
+                quote! {
+                    const REQUIRED: &'static [&'static str] = &[#required_names];
+
+                    #values
+                    match (#required_locals,) {
+                        (#required_values,) => Ok({ #initializer }),
+                        (#required_locals,) => {
+                            let mut missing = ::partial_init_core::MissingFields::new(REQUIRED);
+                            #missing
+                            Err(#missing_err)
+                        }
+                    }
+                }
+            }
+        };
+
+        let mut values = quote! {};
+        values.extend(field_names.iter().zip(locals.iter()).map(|(field, local)| quote! {
+            let #local = ::partial_init_core::MaybeInit::get(self.#field);
+        }));
+
+        let missing_err = if has_validators {
+            quote! { ::partial_init_core::TryBuildError::MissingFields(missing) }
+        } else {
+            quote! { missing }
+        };
+
+        let try_build_body = body(values, missing_err, quote! { ::partial_init_core::TryBuildError::Invalid });
+
+        // the fields are read in order, and the first one that fails is returned
+        let mut values = quote! {};
+        values.extend(field_names.iter().zip(locals.iter()).map(|(field, local)| quote! {
+            let #local = match ::partial_init_core::TryInit::try_get(self.#field) {
+                Ok(#local) => #local,
+                Err(error) => return Err(::partial_init_core::TryInitError::Failed {
+                    field: <#internal_module::#field as ::partial_init_core::FieldName>::INFO,
+                    error,
+                }),
+            };
+        }));
 
-    // Stuff that goes on in the uninit function
-    let (uninit_struct, uninit_struct_initializer) = {
-        let mut uninit_struct = quote! { #lifetimes #generics };
-        uninit_struct.extend(field_names.iter().zip(fields.iter().map(|x| &x.ty)).map(
-            |(x, ty)| {
-                quote! { ::partial_init_core::Uninit<#internal_module::#x, #ty>, }
-            },
-        ));
+        let try_build_fallible_body = body(
+            values,
+            quote! { ::partial_init_core::TryInitError::MissingFields(missing) },
+            quote! { ::partial_init_core::TryInitError::Invalid },
+        );
 
-        let mut initializer = quote! { __phantom_data__partial_init_: Default::default() };
-        initializer.extend(field_names.iter().map(|x| quote! { ,#x: Default::default() }));
-        (uninit_struct, initializer)
+        if required.len() > MAX_REQUIRED_FIELDS {
+            let body = quote! { unreachable!("`TooManyRequiredFields` is never implemented") };
+            (body.clone(), body, Some(quote! { Self: ::partial_init_core::TooManyRequiredFields }))
+        } else {
+            (try_build_body, try_build_fallible_body, None)
+        }
     };
 
-    // Stuff that goes on in the build function
-    let (init_struct, init_struct_initializer) = {
-        let mut init_struct = quote! {};
-        init_struct.extend(ast.generics.lifetimes().map(quote_one));
-        init_struct.extend(ast.generics.type_params().map(quote_one));
-        init_struct.extend(
+    // Stuff that goes on in the build_async function, each field is turned into a
+    // future, and all of them are polled until they are done
+    let (async_struct, async_body) = {
+        let mut async_struct = quote! {};
+        async_struct.extend(ast_generics.lifetimes().map(quote_one));
+        async_struct.extend(type_and_const_params(ast_generics).map(quote_param));
+        async_struct.extend(
             field_names
                 .iter()
                 .zip(fields.iter().map(|field| &field.ty))
                 .zip(attrs.iter())
                 .map(|((field, ty), attr)| {
-                    if let DefaultAttribute::None = &attr.def {
-                        quote! { #field: ::partial_init_core::Init<#internal_module::#field, #ty>, }
+                    if attr.def.is_none() {
+                        quote! { #field: ::partial_init_core::AsyncInit<#internal_module::#field, #ty>, }
                     } else {
-                        quote! { #field: ::partial_init_core::MaybeInit<#internal_module::#field, #ty>, }
+                        quote! { #field: ::partial_init_core::AsyncMaybeInit<#internal_module::#field, #ty>, }
                     }
                 }),
         );
 
-        let mut initializer = quote! {};
-        initializer.extend(field_names.iter().zip(attrs.iter()).zip(old_field_names.iter()).map(|((field, attr), old_field)| {
-            if let DefaultAttribute::Path(def) = &attr.def {
-                quote! { #old_field: ::partial_init_core::MaybeInit::get(self.#field).unwrap_or_else(#def), }
-            } else if let DefaultAttribute::Default = &attr.def {
-                quote! { #old_field: ::partial_init_core::MaybeInit::get(self.#field).unwrap_or_default(), }
+        let mut futures = quote! {};
+        futures.extend(field_names.iter().zip(locals.iter()).zip(attrs.iter()).map(|((field, local), attr)| {
+            let get = if attr.def.is_none() {
+                quote! { ::partial_init_core::AsyncInit::get }
             } else {
-                quote! { #old_field: ::partial_init_core::Init::get(self.#field), }
+                quote! { ::partial_init_core::AsyncMaybeInit::get }
+            };
+
+            quote! {
+                let mut #local = ::partial_init_core::future::MaybeDone::new(#get(self.#field));
             }
         }));
 
-        (init_struct, initializer)
+        // `&=` doesn't short-circuit, so every future is polled
+        let mut polls = quote! {};
+        polls.extend(locals.iter().map(|local| quote! {
+            ready &= #local.poll(cx);
+        }));
+
+        // each one ends in a comma, so that these are still tuples when there is only one field,
+        // and `()` when every field is `#[skip]`
+        let outputs: proc_macro2::TokenStream = locals.iter().map(|local| quote! { #local.take(), }).collect();
+        let values: proc_macro2::TokenStream = locals.iter().map(|local| quote! { Some(#local), }).collect();
+
+        let construct = validated(quote! {});
+        let value = if has_validators {
+            quote! { Ok({ #construct }) }
+        } else {
+            construct
+        };
+
+        let body = quote! {
+            #futures
+            ::partial_init_core::future::poll_fn(move |cx| {
+                let mut ready = true;
+                #polls
+                if !ready {
+                    return ::partial_init_core::future::Poll::Pending;
+                }
+
+                match (#outputs) {
+                    (#values) => ::partial_init_core::future::Poll::Ready((move || {
+                        #defaults
+                        #value
+                    })()),
+                    // the outputs were already taken, so it was polled after it finished
+                    #[allow(unreachable_patterns)]
+                    _ => ::partial_init_core::future::Poll::Pending,
+                }
+            })
+        };
+
+        (async_struct, body)
     };
 
     let internal_module_contents: proc_macro2::TokenStream = {
         let mut internal_module_contents = quote! {};
 
-        internal_module_contents.extend(field_names.iter().map(|field| {
+        internal_module_contents.extend(field_names.iter().zip(fields.iter()).zip(attrs.iter()).zip(field_index.iter()).zip(declared_names.iter()).map(|((((field, field_tok), attr), index), field_name)| {
+            let type_name = type_name(&field_tok.ty);
+            let has_default = !attr.def.is_none();
             let field_doc = format! {
                 " This type represents a field on `{}`, and is not meant to be constructed.",
-                quote! { #name }
+                target_doc
             };
             quote! {
                 /// This type was created by the parital-init-derive crate, see that documentation
@@ -1041,11 +2713,32 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
                 #[doc = #field_doc]
                 /// It is only used as a type-tag.
                 pub enum #field {}
-                impl ::partial_init_core::FieldName for #field { }
+                impl ::partial_init_core::FieldName for #field {
+                    const NAME: &'static str = #field_name;
+                    const INDEX: usize = #index;
+                    const TYPE_NAME: &'static str = #type_name;
+                    const HAS_DEFAULT: bool = #has_default;
+                }
             }
         }));
 
-        let generics = &ast.generics;
+        let field_infos: List<_> = field_names
+            .iter()
+            .map(|field| quote! { <#field as ::partial_init_core::FieldName>::INFO })
+            .collect();
+        let fields_doc = format! {
+            " This table describes all of the fields on `{}`, in the order they were declared.",
+            target_doc
+        };
+        internal_module_contents.extend(std::iter::once(quote! {
+            /// This constant was created by the parital-init-derive crate, see that documentation
+            /// for detail on how this operation works (it's long and detailed)
+            /// 
+            #[doc = #fields_doc]
+            pub const FIELDS: &'static [::partial_init_core::FieldInfo] = &[#field_infos];
+        }));
+
+        let generics = ast_generics;
         let gen = fields
             .iter()
             .map(|field_tok| extract_generics(&field_tok.ty, &generics));
@@ -1055,17 +2748,18 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
             
             let uninit_field_doc = format! {
                 " This type represents an uninitialized field on `{}`.",
-                quote! { #name }
+                target_doc
             };
             temp.extend(field_names.iter().zip(fields.iter()).zip(gen).map(
-                |((field, field_tok), gen)| {
+                |((field, field_tok), (gen, bounds))| {
                     let ty = &field_tok.ty;
                     quote! {
                         /// This type was created by the parital-init-derive crate, see that documentation
                         /// for detail on how this operation works (it's long and detailed)
                         /// 
                         #[doc = #uninit_field_doc]
-                        pub type #field<#gen> = ::partial_init_core::Uninit<super::#field, #ty>;
+                        #[allow(type_alias_bounds)]
+                        pub type #field<#gen> #bounds = ::partial_init_core::Uninit<super::#field, #ty>;
                     }
                 },
             ));
@@ -1093,28 +2787,177 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
     
     let doc_comment1 = format! {
         " This type represents a partially initialized `{name}`, each of the functions",
-        name = quote! { #name }
+        name = target_doc
     };
     
     let doc_comment2 = format! {
         " `{name}`. All builder api functions and FIFs will be marked as such.",
-        name = quote! { #name }
+        name = target_doc
+    };
+
+    // Stuff that goes on in the merge function, the other side gets
+    // its own generic parameter for each field, named `__other_{field}`
+    let (merge_generics, merge_other, merge_output, merge_where_clause, merge_initializer) = {
+        let other_names: Vec<_> = field_names
+            .iter()
+            .map(|field| derived_ident("__other_", field, ""))
+            .collect();
+
+        let mut other_decl = quote! {};
+        other_decl.extend(other_names.iter().zip(field_names.iter()).zip(fields.iter().map(|x| &x.ty)).map(
+            |((other, field), ty)| quote! { #other: ::partial_init_core::MaybeInit<#internal_module::#field, #ty>, }
+        ));
+
+        let where_clause = extend_where_clause(
+            where_clause,
+            other_names.iter().zip(field_names.iter()).zip(fields.iter().map(|x| &x.ty)).map(
+                |((other, field), ty)| quote! { #field: ::partial_init_core::Merge<#internal_module::#field, #ty, #other> }
+            ),
+        );
+
+        let mut output = quote! { #lifetimes #generics };
+        output.extend(other_names.iter().zip(field_names.iter()).zip(fields.iter().map(|x| &x.ty)).map(
+            |((other, field), ty)| quote! {
+                <#field as ::partial_init_core::Merge<#internal_module::#field, #ty, #other>>::Output,
+            }
+        ));
+
+        let mut initializer = quote! { __phantom_data__partial_init_: Default::default() };
+        initializer.extend(field_names.iter().map(|field| quote! {
+            ,#field: ::partial_init_core::Merge::merge(self.#field, other.#field)
+        }));
+
+        let other_args = quote! { #lifetimes #generics #(#other_names),* };
+
+        (other_decl, other_args, output, where_clause, initializer)
     };
 
     let doc_comment3 = format! {
-        " You can create a `{partial_name}` by calling `{name}::uninit()`, then using the relavant functions listed",
+        " You can create a `{partial_name}` by calling `{name}::{uninit_fn}()`, then using the relavant functions listed",
         name = quote! { #name },
+        uninit_fn = quote! { #uninit_fn },
         partial_name = quote! { #partial_name }
     };
 
-    let build_doc = format! {
-        " It is the final step in creating a `{name}`.",
-        name = quote! { #name }
+    let build_doc = if has_validators {
+        format! {
+            " It is the final step in creating a `{name}`, if a validator rejects the value then its error is returned.",
+            name = target_doc
+        }
+    } else {
+        format! {
+            " It is the final step in creating a `{name}`.",
+            name = target_doc
+        }
+    };
+
+    let (build_output, try_build_error) = if has_validators {
+        (
+            quote! { Result<#name #ty_generics, ::partial_init_core::ValidationError> },
+            quote! { ::partial_init_core::TryBuildError },
+        )
+    } else {
+        (quote! { #name #ty_generics }, quote! { ::partial_init_core::MissingFields })
+    };
+
+    let build_async_doc = format! {
+        " It is the asynchronous final step in creating a `{name}`, it awaits all of the futures given",
+        name = target_doc
+    };
+
+    let build_async_doc2 = format! {
+        " by `partial_init_core::Lazy` at the same time, and then builds the value like `{}`.",
+        build
+    };
+
+    let try_build_fallible_doc = format! {
+        " It is the fallible final step in creating a `{name}`, where the fields can be given by sources that can fail with",
+        name = target_doc
+    };
+
+    let try_build_fallible_doc2 = format! {
+        " is returned along with the field it was for, otherwise this is the same as `{}`.",
+        try_build
+    };
+
+    let try_build_where_clause = too_many_required.as_ref().map(|too_many_required| quote! { where #too_many_required });
+    let try_build_fallible_where_clause = extend_where_clause(
+        where_clause,
+        field_names.iter().zip(fields.iter().map(|x| &x.ty)).map(|(field, ty)| quote! {
+            #field: ::partial_init_core::TryInit<#internal_module::#field, #ty, __E>
+        }).chain(too_many_required),
+    );
+
+    let try_build_doc = format! {
+        " It is the fallible final step in creating a `{name}`, if any fields without a default",
+        name = target_doc
+    };
+
+    let try_build_impls = quote! {
+        #[allow(non_camel_case_types)]
+        impl <#generic_decl> #partial_name<#generic_args>
+        #where_clause {
+            /// This function is part of the builder api.
+            #[doc = #try_build_doc]
+            /// are not initialized, then the names of all of those fields are returned.
+            #[inline(always)]
+            #vis fn #try_build(self) -> Result<#name #ty_generics, #try_build_error>
+            #try_build_where_clause {
+                #try_build_body
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl <#state_decl> #partial_name<#generic_args>
+        #where_clause {
+            /// This function is part of the builder api.
+            #[doc = #try_build_fallible_doc]
+            /// `partial_init_core::TryInit`, like `Result` or `fn() -> Result`. The first source that fails
+            #[doc = #try_build_fallible_doc2]
+            #[inline(always)]
+            #vis fn #try_build_fallible<__E>(self) -> Result<#name #ty_generics, ::partial_init_core::TryInitError<__E>>
+            #try_build_fallible_where_clause {
+                #try_build_fallible_body
+            }
+        }
     };
 
     let internal_mod_doc = format! {
         " This module holds types that represent meta-data about `{}` and `{}`",
-        quote! { #name }, quote! { #partial_name }
+        target_doc, quote! { #partial_name }
+    };
+
+    let uninit_impl = if variant.is_some() {
+        let uninit_doc = format! {
+            " It is the first step in creating a `{}`.",
+            target_doc
+        };
+
+        quote! {
+            #[allow(non_camel_case_types)]
+            impl #impl_generics #name #ty_generics
+            #where_clause {
+                /// This function is part of the builder api.
+                #[doc = #uninit_doc]
+                #[inline(always)]
+                #vis fn #uninit_fn() -> #partial_name<#uninit_struct> {
+                    Default::default()
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[allow(non_camel_case_types)]
+            impl #impl_generics ::partial_init_core::PartialInit for #name #ty_generics
+            #where_clause {
+                type Uninitialized = #partial_name<#uninit_struct>;
+
+                #[inline(always)]
+                fn uninit() -> Self::Uninitialized {
+                    Default::default()
+                }
+            }
+        }
     };
 
     let mut expanded = quote! {
@@ -1139,22 +2982,13 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
         /// below to initialize the data.
         #[allow(non_camel_case_types)]
         #[derive(Clone, Copy)]
-        #vis struct #partial_name<#generic_decl>
+        #vis struct #partial_name<#state_decl>
         #where_clause {
-            #struct_fields,
+            #struct_fields
             __phantom_data__partial_init_: ::partial_init_core::PhantomData<(#phantom_data)>
         }
 
-        #[allow(non_camel_case_types)]
-        impl #impl_generics ::partial_init_core::PartialInit for #name #ty_generics
-        #where_clause {
-            type Uninitialized = #partial_name<#uninit_struct>;
-
-            #[inline(always)]
-            fn uninit() -> Self::Uninitialized {
-                Default::default()
-            }
-        }
+        #uninit_impl
 
         impl #impl_generics Default for #partial_name<#uninit_struct>
         #where_clause {
@@ -1172,55 +3006,474 @@ pub fn partial_init_derive(input: TokenStream) -> TokenStream {
             /// This function is part of the builder api.
             #[doc = #build_doc]
             #[inline(always)]
-            #vis fn build(self) -> #name #ty_generics {
-                #name {
-                    #init_struct_initializer
+            #vis fn #build(self) -> #build_output {
+                #init_struct_initializer
+            }
+        }
+
+        #try_build_impls
+
+        #[allow(non_camel_case_types)]
+        impl<#async_struct> #partial_name<#generic_args>
+        #where_clause {
+            /// This function is part of the builder api.
+            #[doc = #build_async_doc]
+            #[doc = #build_async_doc2]
+            #[inline(always)]
+            #vis fn #build_async(self) -> impl ::partial_init_core::future::Future<Output = #build_output> {
+                #async_body
+            }
+        }
+
+        #[allow(non_camel_case_types)]
+        impl <#generic_decl> #partial_name<#generic_args>
+        #where_clause {
+            /// This function is part of the builder api.
+            /// It merges the fields of `other` into this value, a field is initialized
+            /// if it is initialized on either side, and if it is initialized on both sides
+            /// then the value from `self` is kept.
+            #[inline(always)]
+            #vis fn merge<#merge_generics>(self, other: #partial_name<#merge_other>) -> #partial_name<#merge_output>
+            #merge_where_clause {
+                #partial_name {
+                    #merge_initializer
+                }
+            }
+        }
+    };
+
+    // used by the FIFs created by #[nested]
+    expanded.extend(std::iter::once(quote! {
+        #[allow(non_camel_case_types)]
+        impl<#init_struct> ::partial_init_core::Build<#build_output> for #partial_name<#generic_args>
+        #where_clause {
+            #[inline(always)]
+            fn build(self) -> #build_output {
+                #partial_name::#build(self)
+            }
+        }
+    }));
+
+    let type_names = TypeNames {
+        vis,
+        name,
+        target_doc: &target_doc,
+        partial_name: &partial_name,
+        internal_module: &internal_module,
+        dynamic_name: &dynamic_name,
+    };
+    let field_list = FieldList {
+        field_names: &field_names,
+        fields,
+        field_vis: &field_vis,
+        hidden: &hidden,
+        attrs: &attrs,
+    };
+    let generic_list = GenericList {
+        ast_generics,
+        lifetimes: &lifetimes,
+        generics: &generics,
+        generic_decl: &generic_decl,
+        generic_args: &generic_args,
+    };
+
+    let mut deinit_block = quote! {};
+    {
+        let iter = field_initializing_function_impl(
+            type_attr,
+            &mut deinit_block,
+            type_names,
+            field_list,
+            generic_list,
+        );
+
+        expanded.extend(iter);
+    }
+    
+    // the FDFs are only known after all of the FIFs are created
+    if !deinit_block.is_empty() {
+        expanded.extend(std::iter::once(quote! {
+            #[allow(non_camel_case_types)]
+            impl <#state_decl> #partial_name<#generic_args>
+            #where_clause {
+                #deinit_block
+            }
+        }));
+    }
+
+    // the `Deserialize` impls need the `serde` feature of partial-init-core,
+    // this gives a clear error if it is missing
+    #[cfg(feature = "serde")]
+    expanded.extend(std::iter::once(quote! {
+        ::partial_init_core::__require_serde! {}
+    }));
+
+    #[cfg(feature = "serde")]
+    expanded.extend(std::iter::once(deserialize::deserialize_impl(
+        &partial_name,
+        &internal_module,
+
+        &field_names,
+        fields,
+        &hidden,
+
+        ast_generics,
+        &generic_args,
+    )));
+
+    if type_attr.dynamic {
+        #[cfg(feature = "serde")]
+        expanded.extend(std::iter::once(deserialize::deserialize_dynamic_impl(
+            &dynamic_name,
+            &partial_name,
+            fields,
+            ast_generics,
+            &lifetimes,
+            &generics,
+        )));
+
+        expanded.extend(std::iter::once(dynamic::dynamic_builder_impl(
+            type_names,
+            field_list,
+            generic_list,
+            &phantom_data,
+            &build_fns,
+        )?));
+    }
+
+    // println!("{}", expanded);
+
+    Ok(expanded)
+}
+
+// finds an order to evaluate the fields in `build`, so that every field comes after
+// the fields in its `depends_on`, this also resolves the names in `depends_on` to
+// the positions of the fields
+fn evaluation_order(
+    old_field_names: &List<syn::Member>,
+    attrs: &[PartialInitAttribute],
+) -> Result<(Vec<usize>, Vec<Vec<usize>>), Error> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        New,
+        Active,
+        Done,
+    }
+
+    // depth first search in declaration order, so fields without
+    // dependencies keep their place
+    struct Search<'a> {
+        names: Vec<String>,
+        attrs: &'a [PartialInitAttribute],
+        deps: Vec<Vec<usize>>,
+        marks: Vec<Mark>,
+        stack: Vec<usize>,
+        order: Vec<usize>,
+    }
+
+    impl<'a> Search<'a> {
+        fn visit(&mut self, i: usize) -> Result<(), Error> {
+            match self.marks[i] {
+                Mark::Done => return Ok(()),
+                Mark::New => (),
+                Mark::Active => {
+                    let start = self.stack.iter().position(|&j| j == i).unwrap();
+                    let cycle: Vec<_> = self.stack[start..]
+                        .iter()
+                        .chain(std::iter::once(&i))
+                        .map(|&j| format!("`{}`", self.names[j]))
+                        .collect();
+
+                    // the last field on the stack is the one that closed the cycle
+                    let span = match &self.attrs[*self.stack.last().unwrap()].def {
+                        DefaultAttribute::With(_, _, span) => *span,
+                        _ => unreachable!(),
+                    };
+
+                    return Err(Error::new(
+                        span,
+                        format!("cycle in `depends_on`: {}", cycle.join(" -> ")),
+                    ));
+                }
+            }
+
+            self.marks[i] = Mark::Active;
+            self.stack.push(i);
+            for dep in self.deps[i].clone() {
+                self.visit(dep)?;
+            }
+            self.stack.pop();
+            self.marks[i] = Mark::Done;
+            self.order.push(i);
+
+            Ok(())
+        }
+    }
+
+    let names: Vec<String> = old_field_names
+        .iter()
+        .map(|field| match field {
+            syn::Member::Named(ident) => ident.to_string(),
+            syn::Member::Unnamed(index) => format!("_{}", index.index),
+        })
+        .collect();
+
+    let mut deps = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        let mut field_deps = Vec::new();
+        if let DefaultAttribute::With(_, depends_on, _) = &attr.def {
+            for dep in depends_on {
+                match names.iter().position(|name| dep == name) {
+                    Some(i) => field_deps.push(i),
+                    None => return Err(Error::new(dep.span(), format!("no field named `{}`", dep))),
                 }
             }
         }
+        deps.push(field_deps);
+    }
+
+    let mut search = Search {
+        names,
+        attrs,
+        deps,
+        marks: vec![Mark::New; attrs.len()],
+        stack: Vec::new(),
+        order: Vec::with_capacity(attrs.len()),
+    };
+
+    for i in 0..attrs.len() {
+        search.visit(i)?;
+    }
+
+    Ok((search.order, search.deps))
+}
+
+// adds the predicates to the where clause of the type
+fn extend_where_clause<I>(where_clause: Option<&syn::WhereClause>, extra: I) -> proc_macro2::TokenStream
+where
+    I: IntoIterator<Item = proc_macro2::TokenStream>,
+{
+    let mut predicates: List<_> = where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .map(|predicate| quote! { #predicate })
+        .collect();
+    predicates.extend(extra);
+
+    quote! { where #predicates }
+}
+
+// `Option<T>` -> `T`, only looks at the last segment of the path, so `std::option::Option<T>`
+// works too, but a type alias of `Option` doesn't
+fn option_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
     };
 
-    let mut deinit_block = quote! {};
-    let has_deinit_block = {
-        let (iter, has_deinit_block) = field_initializing_function_impl(
-            impl_deinit,
-            &mut deinit_block,
+    let segment = path.segments.iter().last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.iter().next() {
+            Some(syn::GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// `Vec < & 'a E >` -> `Vec<&'a E>`, the token stream puts spaces between every token
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = to_string(ty);
+    let chars: Vec<char> = tokens.chars().collect();
+    let mut type_name = String::with_capacity(tokens.len());
+
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    let is_spaced = |c: char| c == '-' || c == '>' || c == '+' || c == '=';
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' && i != 0 && i + 1 != chars.len() {
+            let (prev, next) = (chars[i - 1], chars[i + 1]);
+
+            let keep = (is_word(prev) && is_word(next))
+                || prev == ',' || prev == ';'
+                || (next == '-' && chars.get(i + 2) == Some(&'>'))
+                || (prev == '>' && chars[i - 2] == '-')
+                || (is_spaced(next) && next != '>' && next != '-')
+                || (is_spaced(prev) && prev != '>' && prev != '-');
+
+            if !keep {
+                continue;
+            }
+        }
+
+        type_name.push(c);
+    }
+
+    type_name
+}
+
+// the FIFs and FDFs are all in the same namespace, so two fields can't end up with
+// the same function, like when #[func_name] or `rename_all` gives two fields the same name
+fn check_function_names(
+    type_attr: &TypeAttribute,
+    field_names: &List<Ident>,
+    attrs: &[PartialInitAttribute],
+    ast_generics: &syn::Generics,
+) -> Result<(), Error> {
+    let mut names: Vec<(String, proc_macro2::Span)> = Vec::new();
+    let deinit_prefix = type_attr.deinit_prefix();
+
+    for (field, attr) in field_names.iter().zip(attrs.iter()) {
+        let span = field.span();
+        names.push((field.to_string(), span));
+
+        if type_attr.deinit || attr.deinit {
+            let deinit_name = derived_name(&deinit_prefix, field, "");
+
+            if syn::parse_str::<Ident>(&deinit_name).is_err() {
+                return Err(Error::new(span, format!("`{}` is not a valid function name", deinit_name)));
+            }
+
+            names.push((deinit_name, span));
+        }
+
+        if type_attr.into || attr.into || attr.strip_option || attr.nested {
+            names.push((derived_name("", field, "_with"), span));
+        }
+
+        if attr.strip_option {
+            names.push((derived_name("maybe_", field, ""), span));
+        }
+
+        if let Some(each) = &attr.each {
+            names.push((each.to_string(), each.span()));
+        }
+
+        names.push((derived_name("set_", field, ""), span));
+        names.push((derived_name("", field, "_or"), span));
+
+        // the fields are also the names of the generic parameters of `Partial*`
+        let shadowed = type_and_const_params(ast_generics).any(|param| match param {
+            syn::GenericParam::Type(ty) => ty.ident == *field,
+            syn::GenericParam::Const(c) => c.ident == *field,
+            syn::GenericParam::Lifetime(_) => false,
+        });
+
+        if shadowed {
+            return Err(Error::new(span, format!("`{}` is already the name of a generic parameter", field)));
+        }
+    }
+
+    for (i, (name, span)) in names.iter().enumerate() {
+        if names[..i].iter().any(|(other, _)| other == name) {
+            return Err(Error::new(*span, format!("duplicate function name `{}`", name)));
+        }
+    }
+
+    // the builder api is defined on the same type as the FIFs and FDFs
+    let build_fns = BuildFns::new(type_attr, proc_macro2::Span::call_site());
+    let mut builder_api = vec![
+        build_fns.build.to_string(),
+        build_fns.try_build.to_string(),
+        build_fns.try_build_fallible.to_string(),
+        build_fns.build_async.to_string(),
+        "merge".to_owned(),
+    ];
+
+    // the setters of the `Dyn*Builder` are named after the FIFs, so they can't clash with its api either
+    if type_attr.dynamic {
+        builder_api.extend(["into_dynamic", "new", "set_field", "into_partial"].iter().map(|name| (*name).to_owned()));
+    }
+
+    for (name, span) in names.iter() {
+        if builder_api.contains(name) {
+            return Err(Error::new(*span, format!("`{}` is already the name of a function in the builder api", name)));
+        }
+    }
+
+    Ok(())
+}
+
+// how far a visibility reaches, `pub(super)` and `pub(in path)` are treated the same
+fn vis_rank(vis: &syn::Visibility) -> u8 {
+    match vis {
+        syn::Visibility::Public(_) => 3,
+        syn::Visibility::Crate(_) => 2,
+        syn::Visibility::Restricted(restricted) => match &*to_string(&restricted.path) {
+            "crate" => 2,
+            "self" => 0,
+            _ => 1,
+        },
+        syn::Visibility::Inherited => 0,
+    }
+}
+
+// the `Partial*` type, the `__*__` module and the `Dyn*Builder` type
+// are all in the same namespace as the type that derives `PartialInit`
+fn check_type_names(
+    type_attr: &TypeAttribute,
+    name: &Ident,
+    partial_name: &Ident,
+    internal_module: &Ident,
+    dynamic_name: &Ident,
+) -> Result<(), Error> {
+    let mut names = vec![(name, "the type")];
+
+    names.push((partial_name, "the `Partial*` type"));
+    names.push((internal_module, "the `__*__` module"));
+
+    if type_attr.dynamic {
+        names.push((dynamic_name, "the `Dyn*Builder` type"));
+    }
+
+    for (i, (ident, _)) in names.iter().enumerate() {
+        if let Some((_, other)) = names[..i].iter().find(|(other, _)| other == ident) {
+            return Err(Error::new(ident.span(), format!("`{}` is already the name of {}", ident, other)));
+        }
+    }
+
+    Ok(())
+}
+
+// the name of a function or generic parameter that is made from the name of a field,
+// raw identifiers lose their `r#`, so `r#type` gets `set_type` instead of `set_r#type`
+fn derived_name(prefix: &str, field: &Ident, suffix: &str) -> String {
+    format!("{}{}{}", prefix, field.to_string().trim_start_matches("r#"), suffix)
+}
 
-            &vis,
-            &partial_name,
-            &internal_module,
+fn derived_ident(prefix: &str, field: &Ident, suffix: &str) -> Ident {
+    Ident::new(&derived_name(prefix, field, suffix), field.span())
+}
 
-            &field_names,
-            &fields,
-            &attrs,
+// `FooBar` -> `foo_bar`, `HTTPRequest` -> `http_request`,
+// used to name the functions created for enum variants
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake_case = String::with_capacity(name.len());
 
-            &lifetimes,
-            &ast.generics,
-            &generics,
-            &generic_args,
-            &where_clause
-        );
-        
-        expanded.extend(iter);
-        has_deinit_block
-    };
-    
-    if has_deinit_block {
-        expanded.extend(std::iter::once(quote! {
-            #[allow(non_camel_case_types)]
-            impl <#generic_decl> #partial_name<#generic_args>
-            #where_clause {
-                #deinit_block
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(next) if next.is_lowercase());
+
+            if prev != '_' && (!prev.is_uppercase() || next_is_lower) {
+                snake_case.push('_');
             }
-        }));
-    }
+        }
 
-    // println!("{}", expanded);
+        snake_case.extend(c.to_lowercase());
+    }
 
-    TokenStream::from(expanded)
+    snake_case
 }
 
-fn extract_generics(ty: &syn::Type, generics: &syn::Generics) -> List<proc_macro2::TokenStream> {
+// the generic parameters of `generics` that are used in `ty`, and the bounds on them,
+// these are used to declare the type aliases in `__{name}__::uninit`
+fn extract_generics(ty: &syn::Type, generics: &syn::Generics) -> (List<proc_macro2::TokenStream>, proc_macro2::TokenStream) {
     use syn::{ConstParam, GenericParam::*, LifetimeDef, TypeParam};
 
     let mut gen: Vec<_> = extract_generics_helper(ty, generics).collect();
@@ -1253,12 +3506,41 @@ fn extract_generics(ty: &syn::Type, generics: &syn::Generics) -> List<proc_macro
         _ => false,
     });
 
-    gen.into_iter()
+    // bounds aren't enforced on type aliases, but they are needed to use associated types
+    // like `T::Item`, so the bounds that only name the parameters of the alias are kept
+    let bounds = generics.type_params()
+        .filter(|param| !param.bounds.is_empty())
+        .map(|param| {
+            let ident = &param.ident;
+            let bounds = &param.bounds;
+            quote! { #ident: #bounds }
+        })
+        .chain(generics.where_clause.iter().flat_map(|where_clause| {
+            where_clause.predicates.iter().map(|predicate| quote! { #predicate })
+        }))
+        .filter(|bound| {
+            let mut found = Vec::new();
+            find_params(bound.clone(), generics, &mut found);
+            !found.is_empty() && found.iter().all(|param| gen.iter().any(|x| std::ptr::eq(*x, *param)))
+        })
+        .collect::<List<_>>();
+
+    let where_clause = if bounds.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #bounds }
+    };
+
+    let gen = gen.into_iter()
             .map(|ty| match ty {
                 syn::GenericParam::Type(syn::TypeParam { ident, .. }) => quote! { #ident },
                 syn::GenericParam::Lifetime(LifetimeDef { lifetime, .. }) => quote! { #lifetime },
-                syn::GenericParam::Const(_) => unimplemented!(),
-            }).collect()
+                syn::GenericParam::Const(ConstParam { const_token, ident, colon_token, ty, .. }) => {
+                    quote! { #const_token #ident #colon_token #ty }
+                }
+            }).collect();
+
+    (gen, where_clause)
 }
 
 /// Super ugly, but working code
@@ -1269,8 +3551,11 @@ fn extract_generics_helper<'a>(
     use syn::{Type::*, *};
 
     match ty {
+        Array(TypeArray { elem, len, .. }) => {
+            Box::new(extract_generics_helper(elem, generics).chain(handle_const_expr(len, generics)))
+        }
+
           Slice(TypeSlice { elem, .. })
-        | Array(TypeArray { elem, .. })
         | Ptr(TypePtr { elem, .. })
         | Paren(TypeParen { elem, .. })
         | Group(TypeGroup { elem, .. }) => extract_generics_helper(&elem, generics),
@@ -1285,7 +3570,10 @@ fn extract_generics_helper<'a>(
         Tuple(TypeTuple { elems, .. }) => 
             Box::new(elems.iter().flat_map(move |x| extract_generics_helper(x, generics))),
         
-        Path(ty) => handle_path(&ty.path, generics),
+        Path(TypePath { qself: Some(qself), path }) => {
+            Box::new(extract_generics_helper(&qself.ty, generics).chain(handle_path(path, generics)))
+        }
+        Path(TypePath { qself: None, path }) => handle_path(path, generics),
         BareFn(ty) => {
             let output = if let syn::ReturnType::Type(_, ty) = &ty.output {
                 Some(extract_generics_helper(&ty, generics))
@@ -1331,6 +3619,40 @@ fn handle_lifetime<'a>(
     pos.map(move |i| &generics.params[i])
 }
 
+// finds the const parameters used in an expression, like the length of an array,
+// the expression is searched token by token, because it can be any expression
+fn handle_const_expr<'a>(
+    expr: &syn::Expr,
+    generics: &'a syn::Generics,
+) -> Box<dyn 'a + Iterator<Item = &'a syn::GenericParam>> {
+    let mut found = Vec::new();
+    find_params(quote! { #expr }, generics, &mut found);
+    Box::new(found.into_iter())
+}
+
+// finds every generic parameter of `generics` that is named in `tokens`,
+// lifetimes are a `'` followed by an identifier
+fn find_params<'a>(tokens: proc_macro2::TokenStream, generics: &'a syn::Generics, found: &mut Vec<&'a syn::GenericParam>) {
+    let mut lifetime = false;
+
+    for token in tokens {
+        match &token {
+            proc_macro2::TokenTree::Group(group) => find_params(group.stream(), generics, found),
+            proc_macro2::TokenTree::Ident(ident) => found.extend(generics.params.iter().filter(|x| match x {
+                syn::GenericParam::Type(ty) => !lifetime && ty.ident == *ident,
+                syn::GenericParam::Const(c) => !lifetime && c.ident == *ident,
+                syn::GenericParam::Lifetime(lt) => lifetime && lt.lifetime.ident == *ident,
+            })),
+            proc_macro2::TokenTree::Punct(_) | proc_macro2::TokenTree::Literal(_) => (),
+        }
+
+        lifetime = match &token {
+            proc_macro2::TokenTree::Punct(punct) => punct.as_char() == '\'',
+            _ => false,
+        };
+    }
+}
+
 fn handle_path<'a>(
     path: &'a syn::Path,
     generics: &'a syn::Generics,
@@ -1339,14 +3661,30 @@ fn handle_path<'a>(
     use syn::*;
 
     Box::new(path.segments.iter().flat_map(move |seg| {
-        let pos = generics.params.iter().position(|x| {
-            if let GenericParam::Type(ty) = x {
-                ty.ident == seg.ident
-            } else {
-                false
-            }
+        // const parameters used as generic arguments, like `Buf<N>`, are parsed as types
+        let pos = generics.params.iter().position(|x| match x {
+            GenericParam::Type(ty) => ty.ident == seg.ident,
+            GenericParam::Const(c) => c.ident == seg.ident,
+            GenericParam::Lifetime(_) => false,
         });
 
+        // `Fn(A) -> B`
+        let parenthesized = if let PathArguments::Parenthesized(ParenthesizedGenericArguments {
+            inputs,
+            output,
+            ..
+        }) = &seg.arguments {
+            let output = if let ReturnType::Type(_, ty) = output {
+                Some(extract_generics_helper(ty, generics))
+            } else {
+                None
+            };
+
+            Some(inputs.iter().map(move |ty| extract_generics_helper(ty, generics)).chain(output).flatten())
+        } else {
+            None
+        };
+
         let args = if let PathArguments::AngleBracketed(AngleBracketedGenericArguments {
             args,
             ..
@@ -1361,6 +3699,7 @@ fn handle_path<'a>(
                         .map(Box::new)
                         .map(|x| x as Box<dyn Iterator<Item = &syn::GenericParam>>)
                 },
+                GenericArgument::Const(expr) => Some(handle_const_expr(expr, generics)),
                 _ => None
             }))
         } else {
@@ -1370,6 +3709,7 @@ fn handle_path<'a>(
         args.into_iter()
             .flatten()
             .flatten()
+            .chain(parenthesized.into_iter().flatten())
             .chain(pos.map(|p| &generics.params[p]))
     }))
 }
@@ -1407,10 +3747,30 @@ fn parse_attributes(fields: &List<syn::Field>) -> Result<Vec<PartialInitAttribut
                 let path = to_string(&a.path);
 
                 match &*path {
-                    "func_name" | "default" | "deinit" => (),
+                    "func_name" | "default" | "deinit" | "into" | "strip_option" | "nested" | "each" | "skip" | "validate" => (),
                     _ => return Err(AttrParseError::NotMine),
                 };
 
+                if path == "default" {
+                    match syn::parse2(tts.clone()) {
+                        Ok(DefaultArgs(Some(def))) => return Ok((span, path, List::new(), Some(def))),
+                        Ok(DefaultArgs(None)) => (),
+                        Err(err) => return Err(AttrParseError::Error(err)),
+                    }
+                }
+
+                if path == "each" {
+                    match syn::parse2(tts.clone()) {
+                        Ok(EachArgs(Some(name))) => {
+                            let mut inside = List::new();
+                            inside.push(syn::Path::from(name));
+                            return Ok((span, path, inside, None));
+                        }
+                        Ok(EachArgs(None)) => (),
+                        Err(err) => return Err(AttrParseError::Error(err)),
+                    }
+                }
+
                 let tts = to_string(tts);
                 let tts = tts.trim();
 
@@ -1422,10 +3782,10 @@ fn parse_attributes(fields: &List<syn::Field>) -> Result<Vec<PartialInitAttribut
                 } else {
                     List::new()
                 };
-                Ok((span, path, inside))
+                Ok((span, path, inside, None))
             })
             .fold(Ok(PartialInitAttribute::default()), move |attr, x| match (attr, x) {
-                (Ok(mut val), Ok((span, path, mut inside))) => {
+                (Ok(mut val), Ok((span, path, mut inside, def))) => {
                     match &*path {
                         "func_name" => {
                             let mut path = inside.pop().unwrap().into_value();
@@ -1441,7 +3801,11 @@ fn parse_attributes(fields: &List<syn::Field>) -> Result<Vec<PartialInitAttribut
                                 return Err(Error::new(span, "expected identifier"));
                             }
 
-                            val.fun = Some(segment.ident);
+                            // the tokens were re-parsed from a string, so the span is lost
+                            val.fun = Some(Ident::new(&segment.ident.to_string(), span));
+                        }
+                        "default" if def.is_some() => {
+                            val.def = def.unwrap();
                         }
                         "default" => {
                             val.def = inside
@@ -1460,6 +3824,84 @@ fn parse_attributes(fields: &List<syn::Field>) -> Result<Vec<PartialInitAttribut
 
                             val.deinit = true;
                         }
+                        "into" => {
+                            if inside.pop().is_some() {
+                                return Err(Error::new(
+                                    span,
+                                    "into takes no arguments",
+                                ));
+                            }
+
+                            val.into = true;
+                        }
+                        "strip_option" => {
+                            if inside.pop().is_some() {
+                                return Err(Error::new(
+                                    span,
+                                    "strip_option takes no arguments",
+                                ));
+                            }
+
+                            if option_type(&field.ty).is_none() {
+                                return Err(Error::new(
+                                    span,
+                                    "strip_option can only be used on `Option` fields",
+                                ));
+                            }
+
+                            val.strip_option = true;
+                        }
+                        "nested" => {
+                            if inside.pop().is_some() {
+                                return Err(Error::new(
+                                    span,
+                                    "nested takes no arguments",
+                                ));
+                            }
+
+                            val.nested = true;
+                        }
+                        "each" => {
+                            let mut path = match inside.pop() {
+                                Some(path) => path.into_value(),
+                                None => return Err(Error::new(span, "expected the name of the FIF")),
+                            };
+
+                            if inside.pop().is_some() {
+                                return Err(Error::new(
+                                    span,
+                                    "please only provide one function name",
+                                ));
+                            }
+
+                            let segment = path.segments.pop().unwrap().into_value();
+                            if path.segments.pop().is_some() {
+                                return Err(Error::new(span, "expected identifier"));
+                            }
+
+                            val.each = Some(segment.ident);
+                        }
+                        "skip" => {
+                            let path = inside.pop().map(syn::punctuated::Pair::into_value);
+                            if inside.pop().is_some() {
+                                return Err(Error::new(
+                                    span,
+                                    "please only provide one default function",
+                                ));
+                            }
+
+                            val.skip = Some(match path {
+                                Some(path) => quote! { #path() },
+                                None => quote! { Default::default() },
+                            });
+                        }
+                        "validate" => {
+                            if inside.is_empty() {
+                                return Err(Error::new(span, "expected the path to a validator"));
+                            }
+
+                            val.validate.extend(inside);
+                        }
                         _ => unreachable!(),
                     }
                     Ok(val)
@@ -1468,41 +3910,71 @@ fn parse_attributes(fields: &List<syn::Field>) -> Result<Vec<PartialInitAttribut
                 (attr, Err(AttrParseError::NotMine)) => attr,
                 (_, Err(AttrParseError::Error(err))) => Err(err),
             })
-    }).collect()
-}
+            .and_then(|mut attr| {
+                use syn::spanned::Spanned;
 
-// 13 arugments, oh god, the joys of extracting code
-// that used to take a lot from the enviornment
-fn field_initializing_function_impl<'a>(
-    impl_deinit: bool,
-    deinit_block: &'a mut proc_macro2::TokenStream,
+                // #[partial_init(..)] on a field
+                for a in field.attrs.iter().filter(|a| to_string(&a.path) == "partial_init") {
+                    let PartialInitArgs(args) = syn::parse2(a.tts.clone())?;
 
-    vis: &'a syn::Visibility,
-    partial_name: &'a syn::Ident,
-    internal_module: &'a syn::Ident,
+                    for arg in args.iter() {
+                        match &*arg.name.to_string() {
+                            "vis" => attr.vis = Some(arg.visibility()?),
+                            _ => return Err(Error::new(arg.name.span(), "unknown `partial_init` argument for a field")),
+                        }
+                    }
+                }
 
-    field_names: &'a List<&'a proc_macro2::Ident>,
-    fields: &'a List<syn::Field>,
-    attrs: &'a [PartialInitAttribute],
+                let only_skip = attr.fun.is_none()
+                    && attr.def.is_none()
+                    && !attr.deinit
+                    && !attr.into
+                    && !attr.strip_option
+                    && !attr.nested
+                    && attr.each.is_none()
+                    && attr.validate.is_empty()
+                    && attr.vis.is_none();
+
+                if attr.skip.is_some() && !only_skip {
+                    return Err(Error::new(field.span(), "`skip` can't be used with other attributes"));
+                }
 
-    lifetimes: &'a List<&'a syn::Lifetime>,
-    ast_generics: &'a syn::Generics,
-    generics: &'a List<&'a syn::Ident>,
-    generic_args: &'a proc_macro2::TokenStream,
-    where_clause: &'a std::option::Option<&syn::WhereClause>,
-) -> (impl 'a + Iterator<Item = proc_macro2::TokenStream>, bool) {
-    let mut has_deinit_block = false;
+                if attr.nested && attr.into {
+                    return Err(Error::new(field.span(), "`into` can't be used with `nested`"));
+                }
+
+                Ok(attr)
+            })
+    }).collect()
+}
 
-    let iter = field_names
+// creates the FIFs, the FDFs are added to `deinit_block`,
+// because they all go in one impl block
+fn field_initializing_function_impl<'a>(
+    type_attr: &'a TypeAttribute,
+    deinit_block: &'a mut proc_macro2::TokenStream,
+    type_names: TypeNames<'a>,
+    field_list: FieldList<'a>,
+    generic_list: GenericList<'a>,
+) -> impl 'a + Iterator<Item = proc_macro2::TokenStream> {
+    let TypeNames { partial_name, internal_module, .. } = type_names;
+    let FieldList { field_names, fields, field_vis, attrs, .. } = field_list;
+    let GenericList { ast_generics, lifetimes, generics, generic_args, .. } = generic_list;
+    let where_clause = &ast_generics.where_clause;
+    let (impl_deinit, impl_into) = (type_attr.deinit, type_attr.into);
+    let deinit_prefix = type_attr.deinit_prefix();
+
+    field_names
         .iter()
         .zip(fields.iter().map(move |field| &field.ty))
         .zip(attrs.iter())
         .enumerate()
         .map(move |(i, ((field, ty), attr))| {
+            let vis = &field_vis[i];
             let (impl_generics, old_generic_args) = {
                 let mut impl_generics = quote! {};
                 impl_generics.extend(ast_generics.lifetimes().map(quote_one));
-                impl_generics.extend(ast_generics.type_params().map(quote_one));
+                impl_generics.extend(type_and_const_params(ast_generics).map(quote_param));
 
                 impl_generics.extend(field_names.iter()
                     .zip(fields.iter().map(|x| &x.ty))
@@ -1512,7 +3984,7 @@ fn field_initializing_function_impl<'a>(
                             None
                         } else {
                             Some(quote! {
-                                #field_j: ::partial_init_core::MaybeInit<#internal_module::#field_j, #ty_j>,
+                                #field_j: ::partial_init_core::FieldState<#internal_module::#field_j, #ty_j>,
                             })
                         }
                     }),
@@ -1559,7 +4031,6 @@ fn field_initializing_function_impl<'a>(
             let func_name = attr.fun.as_ref().unwrap_or(field);
             
             if impl_deinit || attr.deinit {
-                has_deinit_block = true;
                 let deinitializer = {
                     let mut temp = quote! { __phantom_data__partial_init_: Default::default() };
                     
@@ -1584,13 +4055,12 @@ fn field_initializing_function_impl<'a>(
                     quote! { #field }
                 };
 
-                let deinit_func_name = syn::Ident::new(
-                    &format!("deinit_{}", quote! { #func_name }),
-                    func_name.span()
-                );
+                // the fields of tuple structs give names like `deinit__0`
+                let deinit_func_name = derived_ident(&deinit_prefix, func_name, "");
                 deinit_block.extend(std::iter::once(
                     quote! {
                         #[doc = #deinit_func_doc]
+                        #[allow(non_snake_case)]
                         #[inline(always)]
                         #vis fn #deinit_func_name(self) -> #partial_name<#old_generic_args> {
                             #partial_name {
@@ -1611,21 +4081,323 @@ fn field_initializing_function_impl<'a>(
                 if let DefaultAttribute::None = &attr.def { "no" } else { "a" }
             };
 
+            // #[each = name] adds a FIF that adds one item to the field, in any state
+            let each_impl = match &attr.each {
+                Some(each_func_name) => {
+                    let item = quote! { <#ty as IntoIterator>::Item };
+                    let accumulate = quote! {
+                        ::partial_init_core::Accumulate<#internal_module::#field, #ty, #item>
+                    };
+
+                    let each_generic_args = {
+                        let mut temp = quote! { #lifetimes #generics };
+
+                        temp.extend(
+                            field_names
+                                .iter()
+                                .enumerate()
+                                .map(|(j, field_j)| {
+                                    if i == j {
+                                        quote! { <#field as #accumulate>::Output, }
+                                    } else {
+                                        quote! { #field_j, }
+                                    }
+                                })
+                        );
+
+                        temp
+                    };
+
+                    let each_initializer = {
+                        let mut temp = quote! { __phantom_data__partial_init_: Default::default() };
+
+                        temp.extend(
+                            field_names
+                                .iter()
+                                .enumerate()
+                                .map(|(j, field_j)| {
+                                    if i == j {
+                                        quote! { ,#field_j: <#field as #accumulate>::push(self.#field_j, item) }
+                                    } else {
+                                        quote! { ,#field_j: self.#field_j }
+                                    }
+                                })
+                        );
+
+                        temp
+                    };
+
+                    let each_func_doc = format! {
+                        " This is a FIF, it adds an item to `{}`, which can be initialized or not",
+                        quote! { #field }
+                    };
+
+                    quote! {
+                        #[allow(non_camel_case_types)]
+                        impl <#impl_generics #field: #accumulate> #partial_name<#generic_args>
+                        #where_clause {
+                            #[doc = #each_func_doc]
+                            #[inline(always)]
+                            #vis fn #each_func_name(self, item: #item) -> #partial_name<#each_generic_args> {
+                                #partial_name {
+                                    #each_initializer
+                                }
+                            }
+                        }
+                    }
+                }
+                None => quote! {},
+            };
+
+            // #[nested] takes precedence over #[into] on the struct
+            let into = (impl_into || attr.into) && !attr.nested;
+
+            let init_generic_args = {
+                let mut temp = quote! { #lifetimes #generics };
+
+                temp.extend(
+                    field_names
+                        .iter()
+                        .enumerate()
+                        .map(|(j, field_j)| {
+                            if i == j {
+                                quote! { #ty, }
+                            } else {
+                                quote! { #field_j, }
+                            }
+                        })
+                );
+
+                temp
+            };
+
+            // `set_{func_name}` replaces the value of the field in any state, and `{func_name}_or`
+            // only uses the value if the field isn't initialized when it is built, both put an
+            // `Or` in the field, which holds the old state and the new value
+            let set_impl = {
+                let set_func_name = derived_ident("set_", func_name, "");
+                let or_func_name = derived_ident("", func_name, "_or");
+
+                // with #[into] the value is converted right away, with #[strip_option] it is a plain
+                // `Option`, so that `None` isn't ambiguous, and other-wise it must be an `Init`
+                // state, so that the field is always initialized afterwards
+                let (set_generics, set_arg_ty, set_value, value_ty) = if into {
+                    (quote! { <__V: Into<#ty>> }, quote! { __V }, quote! { #field.into() }, quote! { #ty })
+                } else if attr.strip_option {
+                    (quote! {}, quote! { #ty }, quote! { #field }, quote! { #ty })
+                } else {
+                    (
+                        quote! { <__V: ::partial_init_core::Init<#internal_module::#field, #ty>> },
+                        quote! { __V },
+                        quote! { #field },
+                        quote! { __V },
+                    )
+                };
+
+                let or_generic_args = |state: proc_macro2::TokenStream| {
+                    let mut temp = quote! { #lifetimes #generics };
+
+                    temp.extend(
+                        field_names
+                            .iter()
+                            .enumerate()
+                            .map(|(j, field_j)| {
+                                if i == j {
+                                    quote! { ::partial_init_core::Or<#ty, #state, #value_ty>, }
+                                } else {
+                                    quote! { #field_j, }
+                                }
+                            })
+                    );
+
+                    temp
+                };
+                let set_generic_args = or_generic_args(quote! { ::partial_init_core::Uninit<#internal_module::#field, #ty> });
+                let or_generic_args = or_generic_args(quote! { #field });
+
+                let set_func_doc = format! {
+                    " This sets `{}` to a `{}`, whether it is initialized or not",
+                    quote! { #field }, quote! { #ty }
+                };
+
+                let or_func_doc = format! {
+                    " This initializes `{}` with a `{}`, unless it is already initialized when the value is built,",
+                    quote! { #field }, quote! { #ty }
+                };
+
+                quote! {
+                    #[allow(non_camel_case_types)]
+                    impl <#impl_generics #field: ::partial_init_core::FieldState<#internal_module::#field, #ty>> #partial_name<#generic_args>
+                    #where_clause {
+                        #[doc = #set_func_doc]
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        #vis fn #set_func_name #set_generics(self, #field: #set_arg_ty) -> #partial_name<#set_generic_args> {
+                            let #field = ::partial_init_core::Or::new(Default::default(), #set_value);
+
+                            #partial_name {
+                                #initializer
+                            }
+                        }
+
+                        #[doc = #or_func_doc]
+                        /// then the old value is kept, and this value is never read.
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        #vis fn #or_func_name #set_generics(self, #field: #set_arg_ty) -> #partial_name<#or_generic_args> {
+                            let #field = ::partial_init_core::Or::new(self.#field, #set_value);
+
+                            #partial_name {
+                                #initializer
+                            }
+                        }
+                    }
+                }
+            };
+
+            if !(into || attr.strip_option || attr.nested) {
+                return quote! {
+                    #[allow(non_camel_case_types)]
+                    impl <#impl_generics> #partial_name<#old_generic_args>
+                    #where_clause {
+                        #[doc = #func_doc]
+                        #[doc = #default_doc]
+                        #[inline(always)]
+                        #vis fn #func_name<#field: ::partial_init_core::FieldState<#internal_module::#field, #ty>>(self, #field: #field) -> #partial_name<#generic_args> {
+                            #partial_name {
+                                #initializer
+                            }
+                        }
+                    }
+
+                    #each_impl
+                    #set_impl
+                };
+            }
+
+            // with #[into], #[strip_option] or #[nested] the FIF takes a plain value, and the FIF
+            // that takes any `FieldState` is moved to `{func_name}_with`, so that lazy
+            // values can still be used
+            let with_func_name = derived_ident("", func_name, "_with");
+
+            // checked in `parse_attributes`
+            let value_ty = if attr.strip_option { option_type(ty).unwrap() } else { ty };
+
+            let (value_generics, arg_ty, value) = if attr.nested {
+                (
+                    quote! { <__F, __P, __O> },
+                    quote! { __F },
+                    quote! {
+                        ::partial_init_core::Build::build(#field(<#value_ty as ::partial_init_core::PartialInit>::uninit()))
+                    },
+                )
+            } else if into {
+                (quote! { <#field: Into<#value_ty>> }, quote! { #field }, quote! { #field.into() })
+            } else {
+                (quote! {}, quote! { #value_ty }, quote! { #field })
+            };
+
+            // a #[nested] FIF keeps whatever the inner `build` returns, so if the inner type has
+            // validators the field holds a `Result`, which is read by `try_build_fallible`
+            let (value, value_state) = match (attr.strip_option, attr.nested) {
+                (true, true) => (
+                    quote! { ::partial_init_core::InitSome::new(#value) },
+                    quote! { ::partial_init_core::InitSome<#value_ty, __O> },
+                ),
+                (true, false) => (quote! { Some(#value) }, quote! { #ty }),
+                (false, true) => (value, quote! { __O }),
+                (false, false) => (value, quote! { #ty }),
+            };
+
+            let value_generic_args = {
+                let mut temp = quote! { #lifetimes #generics };
+
+                temp.extend(
+                    field_names
+                        .iter()
+                        .enumerate()
+                        .map(|(j, field_j)| {
+                            if i == j {
+                                quote! { #value_state, }
+                            } else {
+                                quote! { #field_j, }
+                            }
+                        })
+                );
+
+                temp
+            };
+
+            // the bounds on the function given to a #[nested] FIF
+            let value_where_clause = if attr.nested {
+                quote! {
+                    where
+                        __F: FnOnce(<#value_ty as ::partial_init_core::PartialInit>::Uninitialized) -> __P,
+                        __P: ::partial_init_core::Build<__O>,
+                        __O: ::partial_init_core::FieldState<#internal_module::#field, #value_ty>
+                }
+            } else {
+                quote! {}
+            };
+
+            let value_func_doc = format! {
+                " This is a FIF, it takes {} `{}` and initializes `{}`",
+                if attr.nested {
+                    "a function that initializes the fields of a"
+                } else if into {
+                    "anything that converts into a"
+                } else {
+                    "a"
+                },
+                quote! { #value_ty }, quote! { #field }
+            };
+
+            let with_func_doc = format! {
+                " This is a FIF, it takes a `{}`, or a lazy value, and initializes `{}`",
+                quote! { #ty }, quote! { #field }
+            };
+
+            let maybe_func = if attr.strip_option {
+                let maybe_func_name = derived_ident("maybe_", func_name, "");
+
+                quote! {
+                    #[doc = #func_doc]
+                    #[doc = #default_doc]
+                    #[inline(always)]
+                    #vis fn #maybe_func_name(self, #field: #ty) -> #partial_name<#init_generic_args> {
+                        self.#with_func_name::<#ty>(#field)
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
             quote! {
                 #[allow(non_camel_case_types)]
                 impl <#impl_generics> #partial_name<#old_generic_args>
                 #where_clause {
-                    #[doc = #func_doc]
+                    #[doc = #value_func_doc]
                     #[doc = #default_doc]
                     #[inline(always)]
-                    #vis fn #func_name<#field: ::partial_init_core::Init<#internal_module::#field, #ty>>(self, #field: #field) -> #partial_name<#generic_args> {
+                    #vis fn #func_name #value_generics(self, #field: #arg_ty) -> #partial_name<#value_generic_args>
+                    #value_where_clause {
+                        self.#with_func_name::<#value_state>(#value)
+                    }
+
+                    #maybe_func
+
+                    #[doc = #with_func_doc]
+                    #[doc = #default_doc]
+                    #[inline(always)]
+                    #vis fn #with_func_name<#field: ::partial_init_core::FieldState<#internal_module::#field, #ty>>(self, #field: #field) -> #partial_name<#generic_args> {
                         #partial_name {
                             #initializer
                         }
                     }
                 }
+
+                #each_impl
+                #set_impl
             }
-        });
-    
-    (iter, has_deinit_block)
+        })
 }
@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate partial_init_derive;
+
+// the setters of `DynConfigBuilder` are named after the fields
+#[derive(PartialInit)]
+#[partial_init(dynamic)]
+pub struct Config {
+    pub new: u8,
+}
+
+#[derive(PartialInit)]
+#[partial_init(dynamic)]
+pub struct Fields {
+    pub set_field: u8,
+}
+
+#[derive(PartialInit)]
+#[partial_init(dynamic)]
+pub struct Partial {
+    pub into_partial: u8,
+}
+
+fn main() {}
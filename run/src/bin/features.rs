@@ -0,0 +1,5 @@
+extern crate run;
+
+fn main() {
+    run::run();
+}
@@ -0,0 +1,99 @@
+// Regression tests for the bounds and where clauses that the derive has to carry over to
+// `Partial*`, the `__{name}__::uninit` aliases, and the FIF and FDF impls, these only have
+// to compile, `run` checks that the values are built correctly.
+
+use partial_init_core::PartialInit;
+use std::fmt::Debug;
+
+// default type parameters
+#[derive(PartialInit, Debug)]
+#[deinit]
+pub struct Defaults<T, U: Default = u32> {
+    pub value: T,
+    #[default]
+    pub other: U,
+}
+
+// `?Sized` and lifetime bounds
+#[derive(PartialInit, Debug)]
+#[deinit]
+pub struct Unsized<'a, T: ?Sized + Debug + 'a> {
+    pub value: &'a T,
+}
+
+// higher-ranked trait bounds, inline and in the where clause
+#[derive(PartialInit)]
+#[deinit]
+pub struct HigherRanked<F: for<'b> Fn(&'b str) -> &'b str, G>
+where
+    G: for<'b> Fn(&'b [u8]) -> Option<&'b u8>,
+{
+    pub trim: F,
+    pub first: G,
+}
+
+// predicates on associated types, and field types that use them
+#[derive(PartialInit)]
+#[deinit]
+pub struct Associated<I>
+where
+    I: Iterator,
+    I::Item: Clone + Debug,
+{
+    pub iter: I,
+    #[default]
+    pub first: Option<I::Item>,
+    #[default]
+    pub items: Vec<<I as Iterator>::Item>,
+}
+
+// where clauses that bound field types instead of parameters
+#[derive(PartialInit, Debug)]
+#[deinit]
+pub struct FieldBounds<T>
+where
+    Vec<T>: Clone + Debug,
+{
+    pub values: Vec<T>,
+}
+
+// type parameters that are only used in parenthesized arguments
+#[derive(PartialInit)]
+#[deinit]
+pub struct Parenthesized<T, U> {
+    pub func: Box<dyn Fn(T) -> U>,
+}
+
+pub fn run() {
+    let value: Defaults<u8> = Defaults::uninit().value(1).build();
+    assert_eq!(value.value, 1);
+    assert_eq!(value.other, 0);
+
+    let value = Defaults::<u8, u8>::uninit().value(1).deinit_value().value(2).other(3).build();
+    assert_eq!(value.value, 2);
+    assert_eq!(value.other, 3);
+
+    let value = Unsized::<str>::uninit().value("unsized").build();
+    assert_eq!(value.value, "unsized");
+
+    type Trim = for<'b> fn(&'b str) -> &'b str;
+    type First = for<'b> fn(&'b [u8]) -> Option<&'b u8>;
+
+    let value = HigherRanked::<Trim, First>::uninit()
+        .trim(str::trim as Trim)
+        .first(<[u8]>::first as First)
+        .build();
+    assert_eq!((value.trim)(" x "), "x");
+    assert_eq!((value.first)(&[1, 2]), Some(&1));
+
+    let value = Associated::<std::vec::IntoIter<i32>>::uninit().iter(vec![1, 2].into_iter()).first(Some(1)).build();
+    assert_eq!(value.first, Some(1));
+    assert_eq!(value.items, Vec::<i32>::new());
+    assert_eq!(value.iter.collect::<Vec<_>>(), [1, 2]);
+
+    let value = FieldBounds::uninit().values(vec![1]).build();
+    assert_eq!(value.values, [1]);
+
+    let value = Parenthesized::uninit().func(Box::new(|x: u8| x as u32 + 1) as Box<dyn Fn(u8) -> u32>).build();
+    assert_eq!((value.func)(1), 2);
+}
@@ -0,0 +1,31 @@
+// const generic parameters are kept on `Partial*` and the `uninit` aliases
+
+use partial_init_core::PartialInit;
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[deinit]
+pub struct Buffer<T, const N: usize> {
+    pub data: [T; N],
+    #[default]
+    pub len: usize,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Grid<'a, T: Copy, const W: usize, const H: usize> {
+    pub cells: &'a [[T; W]; H],
+    #[default(expr = W * H)]
+    pub size: usize,
+}
+
+pub fn run() {
+    let buffer = Buffer::uninit().data([0u8; 16]).len(4).deinit_len().build();
+    assert_eq!(buffer, Buffer { data: [0; 16], len: 0 });
+
+    let uninit: __Buffer__::uninit::data<u8, 16> = Default::default();
+    let buffer = Buffer::uninit().data(uninit).data([1u8; 16]).build();
+    assert_eq!(buffer.data, [1; 16]);
+
+    let cells = [[1u8; 3]; 2];
+    let grid: Grid<u8, 3, 2> = Grid::uninit().cells(&cells).build();
+    assert_eq!(grid.size, 6);
+}
@@ -0,0 +1,71 @@
+// fields accept borrowed values in a `Convert` for some owned types
+
+use partial_init_core::{Convert, PartialInit};
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Owned {
+    pub name: String,
+    pub boxed: Box<u32>,
+    pub c_name: CString,
+    pub os_name: OsString,
+    pub path: PathBuf,
+    #[each = "byte"]
+    pub bytes: Vec<u8>,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Wrapper<T> {
+    pub inner: Box<T>,
+}
+
+pub fn run() {
+    let c_name = CString::new("c").unwrap();
+
+    let owned = Owned::uninit()
+        .name(Convert("name"))
+        .boxed(Convert(1))
+        .c_name(Convert(c_name.as_c_str()))
+        .os_name(Convert(OsStr::new("os")))
+        .path(Convert(Path::new("/tmp")))
+        .bytes(Convert(&[1u8, 2][..]))
+        .build();
+
+    assert_eq!(owned.name, "name");
+    assert_eq!(*owned.boxed, 1);
+    assert_eq!(owned.c_name.as_c_str(), c_name.as_c_str() as &CStr);
+    assert_eq!(owned.os_name, OsString::from("os"));
+    assert_eq!(owned.path, PathBuf::from("/tmp"));
+    assert_eq!(owned.bytes, [1, 2]);
+
+    // items can be added after a conversion
+    let owned = Owned::uninit()
+        .name(Convert("name"))
+        .boxed(Convert(1))
+        .c_name(Convert(c_name.as_c_str()))
+        .os_name(Convert(OsStr::new("os")))
+        .path(Convert(Path::new("/tmp")))
+        .bytes(Convert(&[1u8][..]))
+        .byte(2)
+        .build();
+    assert_eq!(owned.bytes, [1, 2]);
+
+    // without `Convert` only the owned types are accepted
+    let owned = Owned::uninit()
+        .name("name".to_owned())
+        .boxed(Box::new(2))
+        .c_name(c_name.clone())
+        .os_name(OsString::new())
+        .path(PathBuf::new())
+        .bytes(vec![3])
+        .build();
+    assert_eq!((*owned.boxed, owned.bytes), (2, vec![3]));
+
+    // so this is a `Wrapper<u32>`, and not a `Wrapper<Box<u32>>`
+    let wrapper = Wrapper::uninit().inner(Box::new(4u32)).build();
+    assert_eq!(*wrapper.inner, 4);
+
+    let wrapper = Wrapper::uninit().inner(Convert(5u32)).build();
+    assert_eq!(*wrapper.inner, 5);
+}
@@ -0,0 +1,91 @@
+// `#[default]` can use `Default`, a function, a literal or any expression
+
+use block_on;
+use partial_init_core::PartialInit;
+use std::cell::Cell;
+
+thread_local! {
+    static EVALUATED: Cell<u32> = const { Cell::new(0) };
+}
+
+fn range() -> u32 {
+    30
+}
+
+fn counted() -> u32 {
+    EVALUATED.with(|count| count.set(count.get() + 1));
+    1
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    #[default(expr = "Fireball".to_owned())]
+    pub name: String,
+    #[default = 10]
+    pub damage: u32,
+    #[default(range)]
+    pub range: u32,
+    #[default(expr = Vec::with_capacity(4))]
+    pub effects: Vec<&'static str>,
+    #[default]
+    pub cost: u32,
+    #[default(expr = counted())]
+    pub cooldown: u32,
+}
+
+fn display_name(title: &String, name: &String) -> String {
+    format!("{} the {}", name, title)
+}
+
+fn title(level: &u32) -> String {
+    if *level > 10 { "Great".to_owned() } else { "Small".to_owned() }
+}
+
+fn double(value: &u8) -> u8 {
+    value * 2
+}
+
+// the defaults are declared before the fields they depend on, and `name` is
+// listed by its name in the struct, not the name of its FIF
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Hero {
+    #[default(with = display_name, depends_on(title, name))]
+    pub display_name: String,
+    #[default(with = title, depends_on(level))]
+    pub title: String,
+    #[func_name(with_name)]
+    pub name: String,
+    #[default = 1]
+    pub level: u32,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Pair(#[default(with = double, depends_on(_1))] pub u8, pub u8);
+
+pub fn run() {
+    let spell = Spell::uninit().build();
+    assert_eq!(spell.name, "Fireball");
+    assert_eq!((spell.damage, spell.range, spell.cost, spell.cooldown), (10, 30, 0, 1));
+    assert!(spell.effects.is_empty() && spell.effects.capacity() >= 4);
+    assert_eq!(EVALUATED.with(Cell::get), 1);
+
+    // the expression is only evaluated when the field isn't initialized
+    let spell = Spell::uninit().name("Ice".to_owned()).damage(1).cooldown(5).build();
+    assert_eq!((spell.name.as_str(), spell.damage, spell.cooldown), ("Ice", 1, 5));
+    assert_eq!(EVALUATED.with(Cell::get), 1);
+
+    let hero = Hero::uninit().with_name("Ann".to_owned()).level(20).build();
+    assert_eq!((hero.title.as_str(), hero.display_name.as_str()), ("Great", "Ann the Great"));
+
+    let hero = Hero::uninit().with_name("Bo".to_owned()).title("Brave".to_owned()).try_build().unwrap();
+    assert_eq!(hero.display_name, "Bo the Brave");
+
+    // an initialized field doesn't call its default, even if it depends on other fields
+    let hero = Hero::uninit().with_name("Cy".to_owned()).display_name("Cy".to_owned()).build();
+    assert_eq!((hero.title.as_str(), hero.display_name.as_str()), ("Small", "Cy"));
+
+    let hero = block_on(Hero::uninit().with_name("Di".to_owned()).build_async());
+    assert_eq!(hero.display_name, "Di the Small");
+
+    assert_eq!(Pair::uninit()._1(4).build(), Pair(8, 4));
+}
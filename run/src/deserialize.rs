@@ -0,0 +1,63 @@
+// `Partial*` and `Dyn*Builder` types can be read with serde, when the `serde` feature is enabled
+
+use partial_init_core::Uninit;
+use serde_json;
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(dynamic)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    #[default]
+    pub verbose: bool,
+}
+
+type NoVerbose = Uninit<__Config__::verbose, bool>;
+type ConfigLayer = PartialConfig<Option<String>, Option<u16>, Option<bool>>;
+
+// raw identifiers are read and set without their `r#`
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(dynamic)]
+pub struct Raw {
+    pub r#type: String,
+}
+
+pub fn run() {
+    let config: PartialConfig<String, u16, NoVerbose> = serde_json::from_str(r#"{ "host": "localhost", "port": 8080 }"#).unwrap();
+    assert_eq!(
+        config.build(),
+        Config {
+            host: "localhost".to_owned(),
+            port: 8080,
+            verbose: false,
+        }
+    );
+
+    // a field that is set in the state must be present, and one that isn't must be absent
+    assert!(serde_json::from_str::<PartialConfig<String, u16, NoVerbose>>(r#"{ "host": "localhost" }"#).is_err());
+    assert!(serde_json::from_str::<PartialConfig<String, Uninit<__Config__::port, u16>, NoVerbose>>(r#"{ "host": "localhost", "port": 1 }"#).is_err());
+    assert!(serde_json::from_str::<ConfigLayer>(r#"{ "hostname": "localhost" }"#).is_err());
+    assert!(serde_json::from_str::<ConfigLayer>(r#"{ "port": 1, "port": 2 }"#).is_err());
+
+    let defaults: ConfigLayer = serde_json::from_str(r#"{ "port": 8080 }"#).unwrap();
+    let overlay: ConfigLayer = serde_json::from_str(r#"{ "host": "localhost" }"#).unwrap();
+    let config = overlay.merge(defaults).into_dynamic().build().unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+
+    let builder: DynConfigBuilder = serde_json::from_str(r#"{ "port": 80, "verbose": true }"#).unwrap();
+    assert!(builder.build().is_err());
+
+    let mut builder: DynConfigBuilder = serde_json::from_str(r#"{ "port": 80, "verbose": true }"#).unwrap();
+    builder.host("example.com".to_owned());
+    let config = builder.build().unwrap();
+    assert_eq!(config.port, 80);
+    assert!(config.verbose);
+
+    let raw: PartialRaw<String> = serde_json::from_str(r#"{ "type": "fire" }"#).unwrap();
+    assert_eq!(raw.build(), Raw { r#type: "fire".to_owned() });
+
+    let mut builder = DynRawBuilder::new();
+    builder.set_field("type", Box::new("ice".to_owned())).unwrap();
+    assert_eq!(builder.build().unwrap(), Raw { r#type: "ice".to_owned() });
+}
@@ -0,0 +1,69 @@
+// `Dyn*Builder`s check which fields are set at run-time
+
+use partial_init_core::{BuildError, FieldName, PartialInit, Uninit};
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(dynamic)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    #[default]
+    pub verbose: bool,
+}
+
+pub fn run() {
+    let mut builder = DynConfigBuilder::new();
+    builder.set_field("host", Box::new("localhost".to_owned())).unwrap();
+    builder.set_field("verbose", Box::new(true)).unwrap();
+
+    match builder.set_field("port", Box::new(8080u32)) {
+        Err(BuildError::WrongType(info)) => assert_eq!(info, <__Config__::port as FieldName>::INFO),
+        other => panic!("expected a `u32` to be rejected, got {:?}", other),
+    }
+    match builder.set_field("address", Box::new(0u16)) {
+        Err(BuildError::UnknownField(name)) => assert_eq!(name, "address"),
+        other => panic!("expected `address` to be unknown, got {:?}", other),
+    }
+
+    builder.port(8080);
+    let config = builder.build().unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "localhost".to_owned(),
+            port: 8080,
+            verbose: true,
+        }
+    );
+
+    // `None` unsets a field, and the missing fields are reported by `build`
+    let mut builder = DynConfigBuilder::new();
+    builder.host("localhost".to_owned()).host(None);
+    match builder.build() {
+        Err(BuildError::MissingFields(missing)) => {
+            assert_eq!(missing.len(), 2);
+            assert!(missing.contains("host") && missing.contains("port"));
+        }
+        other => panic!("expected missing fields, got {:?}", other),
+    }
+
+    // a `Partial*` can go through the dynamic builder and back, if the state matches
+    let mut builder = Config::uninit().host("localhost".to_owned()).into_dynamic();
+    builder.port(80);
+    let partial: PartialConfig<String, u16, Option<bool>> = builder.into_partial().unwrap();
+    assert_eq!(partial.build().port, 80);
+
+    let builder = Config::uninit().host("localhost".to_owned()).verbose(true).into_dynamic();
+    let error = match builder.into_partial::<PartialConfig<Uninit<__Config__::host, String>, u16, bool>>() {
+        Ok(_) => panic!("`host` is set and `port` isn't, so the state shouldn't match"),
+        Err(error) => error,
+    };
+    assert_eq!(error.fields.len(), 2);
+    assert!(error.fields.contains("host") && error.fields.contains("port"));
+
+    // the builder is given back unchanged
+    let mut builder = error.builder;
+    builder.port(1);
+    let config = builder.build().unwrap();
+    assert_eq!((config.host.as_str(), config.port, config.verbose), ("localhost", 1, true));
+}
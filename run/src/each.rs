@@ -0,0 +1,48 @@
+// `#[each]` FIFs add one item at a time to a collection field
+
+use partial_init_core::PartialInit;
+use std::collections::{BTreeSet, HashMap};
+
+fn base_effects() -> Vec<&'static str> {
+    vec!["base"]
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    #[each = "effect"]
+    #[default]
+    pub effects: Vec<&'static str>,
+    #[each(tag)]
+    pub tags: BTreeSet<u8>,
+    #[each(stat)]
+    #[default]
+    pub stats: HashMap<&'static str, u32>,
+}
+
+pub fn run() {
+    let spell = Spell::uninit()
+        .name("fire".to_owned())
+        .effect("burning")
+        .tag(2)
+        .effect("heat stroke")
+        .tag(1)
+        .stat(("damage", 20))
+        .build();
+    assert_eq!(spell.effects, ["burning", "heat stroke"]);
+    assert_eq!(spell.tags.into_iter().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(spell.stats["damage"], 20);
+
+    // items added to an initialized field are added to its value when it is built
+    let spell = Spell::uninit()
+        .name("ice".to_owned())
+        .effects(base_effects as fn() -> Vec<&'static str>)
+        .effect("frozen")
+        .tags(BTreeSet::new())
+        .build();
+    assert_eq!(spell.effects, ["base", "frozen"]);
+    assert!(spell.tags.is_empty());
+
+    // the first item initializes a field without a default
+    assert!(Spell::uninit().name("bolt".to_owned()).tag(3).try_build().is_ok());
+}
@@ -0,0 +1,41 @@
+// enums get one `Partial*` type for each variant that has fields
+
+use partial_init_core::FieldName;
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub enum Message {
+    Login {
+        user: String,
+        #[default]
+        password: Option<String>,
+    },
+    #[deinit]
+    Logout(u32),
+    Quit,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub enum Shape<T> {
+    Circle { radius: T },
+    Rect { width: T, height: T },
+}
+
+pub fn run() {
+    let login = Message::uninit_login().user("Ozaren".to_owned()).build();
+    assert_eq!(
+        login,
+        Message::Login {
+            user: "Ozaren".to_owned(),
+            password: None,
+        }
+    );
+
+    let logout = Message::uninit_logout()._0(10).deinit__0()._0(20).build();
+    assert_eq!(logout, Message::Logout(20));
+
+    assert_eq!(<__MessageLogin__::user as FieldName>::NAME, "user");
+
+    let rect: Shape<f32> = Shape::uninit_rect().width(2.0).height(3.0).build();
+    assert_eq!(rect, Shape::Rect { width: 2.0, height: 3.0 });
+    assert_eq!(Shape::uninit_circle().radius(1).build(), Shape::Circle { radius: 1 });
+}
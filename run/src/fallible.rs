@@ -0,0 +1,52 @@
+// fallible sources are read by `try_build_fallible`, which returns the first error
+
+use partial_init_core::{FieldName, PartialInit, TryInitError};
+use std::num::ParseIntError;
+
+fn read_range() -> Result<u32, ParseIntError> {
+    "12".parse()
+}
+
+fn read_broken() -> Result<u32, ParseIntError> {
+    "twelve".parse()
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    pub damage: u32,
+    #[default = 1]
+    pub range: u32,
+}
+
+pub fn run() {
+    let spell = Spell::uninit()
+        .name("fire".to_owned())
+        .damage("20".parse::<u32>())
+        .range(read_range as fn() -> Result<u32, ParseIntError>)
+        .try_build_fallible()
+        .unwrap();
+    assert_eq!((spell.damage, spell.range), (20, 12));
+
+    // the fields are read in order, so `damage` fails before `range`
+    let error = Spell::uninit()
+        .name("fire".to_owned())
+        .damage("x".parse::<u32>())
+        .range(read_broken as fn() -> Result<u32, ParseIntError>)
+        .try_build_fallible()
+        .unwrap_err();
+    match error {
+        TryInitError::Failed { field, .. } => assert_eq!(field, <__Spell__::damage as FieldName>::INFO),
+        other => panic!("expected `damage` to fail, got {:?}", other),
+    }
+
+    let error = Spell::uninit()
+        .name(None)
+        .damage(Ok::<u32, ParseIntError>(1))
+        .try_build_fallible()
+        .unwrap_err();
+    match error {
+        TryInitError::MissingFields(missing) => assert!(missing.contains("name")),
+        other => panic!("expected `name` to be missing, got {:?}", other),
+    }
+}
@@ -0,0 +1,40 @@
+// `#[into]` FIFs take anything that converts into the field, `{name}_with` takes any state
+
+use partial_init_core::PartialInit;
+
+fn default_name() -> String {
+    "lazy".to_owned()
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    #[into]
+    #[func_name(with_name)]
+    pub name: String,
+    pub damage: u32,
+}
+
+// on the type, every field converts
+#[derive(PartialInit, Debug, PartialEq)]
+#[into]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+pub fn run() {
+    let spell = Spell::uninit().with_name("fire").damage(20).build();
+    assert_eq!(spell.name, "fire");
+
+    let spell = Spell::uninit()
+        .with_name_with(default_name as fn() -> String)
+        .damage(20)
+        .build();
+    assert_eq!(spell.name, "lazy");
+
+    let spell = Spell::uninit().with_name_with(None).damage(1).try_build();
+    assert!(spell.is_err());
+
+    let point = Point::uninit().x(1u8).y(2.5f32).build();
+    assert_eq!(point, Point { x: 1.0, y: 2.5 });
+}
@@ -0,0 +1,62 @@
+// `build_async` awaits the `Lazy` futures given to the FIFs
+
+use block_on;
+use partial_init_core::future::{ready, Context, Future, Poll};
+use partial_init_core::{Lazy, PartialInit};
+use std::pin::Pin;
+
+// a future that is pending for the first `self.0` polls
+pub struct Countdown<T>(u32, Option<T>);
+
+impl<T: Unpin> Future for Countdown<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        if self.0 == 0 {
+            Poll::Ready(self.1.take().expect("polled after it finished"))
+        } else {
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    pub damage: u32,
+    #[default = 5]
+    pub range: u32,
+}
+
+pub fn run() {
+    let spell = Spell::uninit()
+        .name(Lazy::future(Countdown(3, Some("fire".to_owned()))))
+        .damage(Lazy::future(ready(20)))
+        .build_async();
+    let spell = block_on(spell);
+    assert_eq!(
+        spell,
+        Spell {
+            name: "fire".to_owned(),
+            damage: 20,
+            range: 5,
+        }
+    );
+
+    // plain values and defaults work too, and `Lazy` can be used for a field with a default
+    let spell = Spell::uninit()
+        .name("ice".to_owned())
+        .damage(1)
+        .range(Lazy::future(Countdown(1, Some(9))))
+        .build_async();
+    assert_eq!(block_on(spell).range, 9);
+
+    // futures that aren't `Unpin`, like `async` blocks, can be given in a pinned box
+    let spell = Spell::uninit()
+        .name(Lazy::future(Box::pin(Countdown(2, Some("bolt".to_owned())))))
+        .damage(2)
+        .build_async();
+    assert_eq!(block_on(spell).name, "bolt");
+}
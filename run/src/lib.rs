@@ -0,0 +1,81 @@
+// Checks that the code the derive generates compiles and behaves as documented,
+// each module covers one feature and panics if something is wrong.
+// `cargo run --bin features` runs all of them.
+
+#[macro_use]
+extern crate partial_init_derive;
+extern crate partial_init_core;
+extern crate serde_json;
+
+use partial_init_core::future::{Context, Future, Poll};
+use std::task::{RawWaker, RawWakerVTable, Waker};
+
+pub mod bounds;
+pub mod const_generics;
+pub mod conversions;
+pub mod defaults;
+pub mod deserialize;
+pub mod dynamic;
+pub mod each;
+pub mod enums;
+pub mod fallible;
+pub mod into;
+pub mod lazy;
+pub mod merge;
+pub mod meta;
+pub mod naming;
+pub mod nested;
+pub mod options;
+pub mod setters;
+pub mod skip;
+pub mod try_build;
+pub mod tuple;
+pub mod validate;
+pub mod visibility;
+
+pub fn run() {
+    bounds::run();
+    const_generics::run();
+    conversions::run();
+    defaults::run();
+    deserialize::run();
+    dynamic::run();
+    each::run();
+    enums::run();
+    fallible::run();
+    into::run();
+    lazy::run();
+    merge::run();
+    meta::run();
+    naming::run();
+    nested::run();
+    options::run();
+    setters::run();
+    skip::run();
+    try_build::run();
+    tuple::run();
+    validate::run();
+    visibility::run();
+}
+
+// runs a future on the current thread, the futures in these checks never wait on anything
+pub fn block_on<F: Future + Unpin>(mut fut: F) -> F::Output {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(value) = std::pin::Pin::new(&mut fut).poll(&mut cx) {
+            return value;
+        }
+    }
+}
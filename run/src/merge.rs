@@ -0,0 +1,43 @@
+// `merge` combines two partial values field-by-field, `self` wins when both are set
+
+use partial_init_core::PartialInit;
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    #[default]
+    pub verbose: bool,
+}
+
+// raw identifiers lose their `r#` in the names made from them
+#[derive(PartialInit, Debug, PartialEq)]
+#[deinit]
+pub struct Raw {
+    pub r#type: u32,
+    #[into]
+    pub r#ref: String,
+    #[strip_option]
+    pub r#loop: Option<u8>,
+}
+
+pub fn run() {
+    let user = Config::uninit().port(80);
+    let file = Config::uninit().host("file".to_owned()).port(8080);
+    let config = user.merge(file).build();
+    assert_eq!(config, Config { host: "file".to_owned(), port: 80, verbose: false });
+
+    // `other` isn't evaluated if `self` is set
+    let port: fn() -> u16 = || panic!("`other` was evaluated");
+    let config = Config::uninit().port(1).merge(Config::uninit().host("h".to_owned()).port(port)).build();
+    assert_eq!(config.port, 1);
+
+    // an `Option` on the left is only known at run-time
+    let config = Config::uninit().port(None).merge(Config::uninit().host("h".to_owned()).port(2)).try_build();
+    assert_eq!(config.map(|config| config.port).ok(), Some(2));
+
+    let left = Raw::uninit().r#type(1).ref_with("left".to_owned()).maybe_loop(None).deinit_type();
+    let right = Raw::uninit().r#type(2).r#ref("right").r#loop(3);
+    let raw = left.merge(right).build();
+    assert_eq!(raw, Raw { r#type: 2, r#ref: "left".to_owned(), r#loop: None });
+}
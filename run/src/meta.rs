@@ -0,0 +1,45 @@
+// every field is described at run-time by `FieldName` and `__{name}__::FIELDS`
+
+use partial_init_core::{FieldInfo, FieldName};
+
+#[derive(PartialInit)]
+pub struct Spell<T> {
+    #[func_name(with_name)]
+    pub name: String,
+    pub damage: Vec<T>,
+    #[default]
+    pub range: u32,
+}
+
+// `NAME` doesn't have the `r#` of a raw identifier
+#[derive(PartialInit)]
+pub struct Raw {
+    pub r#type: u8,
+}
+
+pub fn run() {
+    // `NAME` is the declared name, not the name from `#[func_name]`
+    assert_eq!(<__Spell__::with_name as FieldName>::NAME, "name");
+    assert_eq!(<__Spell__::damage as FieldName>::INDEX, 1);
+    assert_eq!(<__Spell__::damage as FieldName>::TYPE_NAME, "Vec<T>");
+    assert_eq!(
+        (<__Spell__::damage as FieldName>::HAS_DEFAULT, <__Spell__::range as FieldName>::HAS_DEFAULT),
+        (false, true)
+    );
+
+    assert_eq!(
+        <__Spell__::range as FieldName>::INFO,
+        FieldInfo {
+            name: "range",
+            index: 2,
+            type_name: "u32",
+            has_default: true,
+        }
+    );
+
+    let names: Vec<_> = __Spell__::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["name", "damage", "range"]);
+    assert_eq!(__Spell__::FIELDS[1], <__Spell__::damage as FieldName>::INFO);
+
+    assert_eq!(<__Raw__::r#type as FieldName>::NAME, "type");
+}
@@ -0,0 +1,81 @@
+// the names of the FIFs, and of the generated items, can be changed with `#[partial_init(..)]`
+
+// `rename_all = "camelCase"` makes FIFs that aren't snake case
+#![allow(non_snake_case)]
+
+use block_on;
+use partial_init_core::{FieldName, PartialInit};
+use serde_json;
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[deinit]
+#[partial_init(prefix = "with_")]
+pub struct Spell {
+    pub name: String,
+    #[func_name(damage)]
+    pub base_damage: u32,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(rename_all = "camelCase", prefix = "set", dynamic)]
+pub struct Window {
+    pub inner_width: u32,
+    #[default]
+    pub is_visible: bool,
+}
+
+// `partial`, `module`, `build_fn` and `deinit_prefix` rename the generated items
+#[derive(PartialInit, Debug, PartialEq)]
+#[deinit]
+#[partial_init(partial = SpellBuilder, module = spell_fields, build_fn = finish, deinit_prefix = "clear_")]
+pub struct Named {
+    pub name: String,
+    pub build: u32,
+    #[default]
+    pub level: u8,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Book {
+    #[nested]
+    pub named: Named,
+}
+
+pub fn run() {
+    let spell = Spell::uninit()
+        .with_name("fire".to_owned())
+        .damage(20)
+        .deinit_with_name()
+        .with_name("ice".to_owned())
+        .build();
+    assert_eq!((spell.name.as_str(), spell.base_damage), ("ice", 20));
+    // only the functions are renamed, `NAME` is still the name of the field
+    assert_eq!(<__Spell__::with_name as FieldName>::NAME, "name");
+    assert_eq!(<__Spell__::damage as FieldName>::NAME, "base_damage");
+
+    let window = Window::uninit().setinnerWidth(640).setisVisible(true).build();
+    assert_eq!((window.inner_width, window.is_visible), (640, true));
+    assert_eq!(<__Window__::setinnerWidth as FieldName>::NAME, "inner_width");
+
+    // so serde and `set_field` use the names of the fields too
+    let window: PartialWindow<u32, bool> = serde_json::from_str(r#"{ "inner_width": 800, "is_visible": false }"#).unwrap();
+    assert_eq!(window.build(), Window { inner_width: 800, is_visible: false });
+
+    let mut builder = DynWindowBuilder::new();
+    builder.set_field("inner_width", Box::new(1024u32)).unwrap();
+    assert_eq!(builder.build().unwrap().inner_width, 1024);
+
+    let builder: SpellBuilder<String, spell_fields::uninit::build, spell_fields::uninit::level> =
+        Named::uninit().name("fire".to_owned());
+    let named = builder.build(20).clear_build().build(30).finish();
+    assert_eq!((named.name.as_str(), named.build), ("fire", 30));
+    assert_eq!(<spell_fields::build as FieldName>::NAME, "build");
+
+    assert!(Named::uninit().name("ice".to_owned()).try_finish().is_err());
+    let named = block_on(Named::uninit().name("ice".to_owned()).build(1).finish_async());
+    assert_eq!(named.build, 1);
+
+    // `#[nested]` goes through `Build`, so it doesn't need the name of the final step
+    let book = Book::uninit().named(|named| named.name("bolt".to_owned()).build(2)).build();
+    assert_eq!(book.named.build, 2);
+}
@@ -0,0 +1,85 @@
+// `#[nested]` fields are built with the builder of their own type
+
+use block_on;
+use partial_init_core::{FieldName, PartialInit, TryInitError, ValidationError};
+
+fn not_zero(value: &u32) -> Result<(), &'static str> {
+    if *value == 0 {
+        Err("must not be zero")
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Cost {
+    pub mana: u32,
+    #[default]
+    pub health: u32,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Cooldown {
+    #[validate(not_zero)]
+    pub seconds: u32,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    #[nested]
+    pub cost: Cost,
+    #[nested]
+    #[strip_option]
+    #[default]
+    pub refund: Option<Cost>,
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Ability {
+    #[nested]
+    pub cooldown: Cooldown,
+    #[nested]
+    #[strip_option]
+    #[default]
+    pub charge: Option<Cooldown>,
+}
+
+pub fn run() {
+    let spell = Spell::uninit()
+        .name("fire".to_owned())
+        .cost(|cost| cost.mana(20))
+        .refund(|cost| cost.mana(5).health(1))
+        .build();
+    assert_eq!(spell.cost, Cost { mana: 20, health: 0 });
+    assert_eq!(spell.refund, Some(Cost { mana: 5, health: 1 }));
+
+    let spell = Spell::uninit()
+        .name("ice".to_owned())
+        .cost(|cost| cost.mana(1))
+        .refund(|cost| cost.mana(2))
+        .build_async();
+    let spell = block_on(spell);
+    assert_eq!(spell.refund, Some(Cost { mana: 2, health: 0 }));
+
+    // with validators on the inner type, the field holds the `Result` of the inner build
+    let ability = Ability::uninit()
+        .cooldown(|cooldown| cooldown.seconds(3))
+        .charge(|cooldown| cooldown.seconds(1))
+        .try_build_fallible::<ValidationError>()
+        .unwrap();
+    assert_eq!(ability.cooldown, Cooldown { seconds: 3 });
+    assert_eq!(ability.charge, Some(Cooldown { seconds: 1 }));
+
+    let ability = Ability::uninit()
+        .cooldown(|cooldown| cooldown.seconds(3))
+        .charge(|cooldown| cooldown.seconds(0))
+        .try_build_fallible();
+    match ability {
+        Err(TryInitError::Failed { field, error }) => {
+            assert_eq!(field, <__Ability__::charge as FieldName>::INFO);
+            assert_eq!(error.field, Some(<__Cooldown__::seconds as FieldName>::INFO));
+        }
+        _ => panic!("the inner validator should have failed"),
+    }
+}
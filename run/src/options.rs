@@ -0,0 +1,41 @@
+// `#[strip_option]` FIFs take the `T` of an `Option<T>` field, and `maybe_{name}` takes the `Option`
+
+use partial_init_core::PartialInit;
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell<T> {
+    pub name: String,
+    #[strip_option]
+    #[default]
+    pub bonus: Option<T>,
+    #[strip_option]
+    #[into]
+    #[default]
+    pub note: Option<String>,
+}
+
+fn bonus() -> Option<char> {
+    Some('x')
+}
+
+pub fn run() {
+    let spell: Spell<u32> = Spell::uninit().name("fire".to_owned()).bonus(10).note("hot").build();
+    assert_eq!((spell.bonus, spell.note.as_deref()), (Some(10), Some("hot")));
+
+    let spell = Spell::uninit().name("ice".to_owned()).maybe_bonus(None::<u8>).build();
+    assert_eq!((spell.bonus, spell.note), (None, None));
+
+    let spell = Spell::uninit()
+        .name("bolt".to_owned())
+        .maybe_bonus(Some(1u8))
+        .maybe_note(None)
+        .build();
+    assert_eq!(spell.bonus, Some(1));
+
+    // `{name}_with` takes any state of the `Option`
+    let spell: Spell<char> = Spell::uninit()
+        .name("wind".to_owned())
+        .bonus_with(bonus as fn() -> Option<char>)
+        .build();
+    assert_eq!(spell.bonus, Some('x'));
+}
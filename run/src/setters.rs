@@ -0,0 +1,61 @@
+// `set_{field}` and `{field}_or` put an `Or` in the field, which is only read when the value is built
+
+use block_on;
+use partial_init_core::future::ready;
+use partial_init_core::{Lazy, PartialInit, TryInitError};
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    #[into]
+    pub name: String,
+    #[default]
+    pub damage: u32,
+    #[strip_option]
+    #[default]
+    pub note: Option<String>,
+}
+
+// the FIF of `set_x` would have the same name as the setter of `x`
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Point {
+    pub x: u8,
+    #[func_name(x_again)]
+    pub set_x: u8,
+}
+
+fn unused() -> u32 {
+    panic!("the fallback was read even though the field was initialized")
+}
+
+pub fn run() {
+    let spell = Spell::uninit().name("fire").set_name("ice").damage_or(10).build();
+    assert_eq!(spell, Spell { name: "ice".to_owned(), damage: 10, note: None });
+
+    // the fallback is only read if the field isn't initialized
+    let spell = Spell::uninit().name("fire").damage(1).damage_or(unused as fn() -> u32).build();
+    assert_eq!(spell.damage, 1);
+
+    let spell = Spell::uninit().name_or("fire").damage(None).damage_or((|| 20) as fn() -> u32).build();
+    assert_eq!((spell.name.as_str(), spell.damage), ("fire", 20));
+
+    let spell = Spell::uninit().name("fire").note_or(Some("old".to_owned())).set_note(None).build();
+    assert_eq!(spell.note, None);
+
+    // lazy and fallible old states keep their state
+    let partial = Spell::uninit().name("fire").damage(Lazy::future(ready(30))).damage_or(1);
+    assert_eq!(block_on(partial.build_async()).damage, 30);
+
+    let damage: Result<u32, &str> = Err("no damage");
+    let error = Spell::uninit().name("fire").damage(damage).damage_or(1).try_build_fallible().unwrap_err();
+    match error {
+        TryInitError::Failed { field, error } => assert_eq!((field.name, error), ("damage", "no damage")),
+        _ => panic!("expected the damage to fail"),
+    }
+
+    // the field is initialized after `{field}_or`, so it wins in `merge`
+    let spell = Spell::uninit().damage(None).damage_or(40).merge(Spell::uninit().name("fire").damage(50)).build();
+    assert_eq!(spell.damage, 40);
+
+    let point = Point::uninit().x(1).x_again(2).set_x(3).set_x_again(4).build();
+    assert_eq!(point, Point { x: 3, set_x: 4 });
+}
@@ -0,0 +1,47 @@
+// `#[skip]` fields are only filled in when the value is built
+
+use partial_init_core::{FieldName, PartialInit};
+use std::marker::PhantomData;
+
+fn start_count() -> u32 {
+    7
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell<T> {
+    #[skip]
+    cache: Vec<u8>,
+    pub name: String,
+    #[skip(start_count)]
+    casts: u32,
+    #[default]
+    pub damage: u32,
+    #[skip]
+    marker: PhantomData<T>,
+}
+
+pub fn run() {
+    let spell: Spell<()> = Spell::uninit().name("fire".to_owned()).build();
+    assert_eq!(spell.name, "fire");
+    assert_eq!(spell.damage, 0);
+    assert_eq!(spell.cache, Vec::<u8>::new());
+    assert_eq!(spell.casts, 7);
+    assert_eq!(spell.marker, PhantomData);
+
+    // the skipped fields still count towards the position in the declaration
+    assert_eq!(<__Spell__::name as FieldName>::INDEX, 1);
+    assert_eq!(<__Spell__::damage as FieldName>::INDEX, 3);
+    assert_eq!(<__Spell__::damage as FieldName>::INFO.index, 3);
+
+    let hidden = Hidden::uninit().build();
+    assert_eq!(hidden, Hidden { cache: Vec::new(), casts: 7 });
+}
+
+// every field is `#[skip]`, so the builder has nothing to initialize
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Hidden {
+    #[skip]
+    cache: Vec<u8>,
+    #[skip(start_count)]
+    casts: u32,
+}
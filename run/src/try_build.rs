@@ -0,0 +1,48 @@
+// `try_build` reports every field without a default that isn't initialized
+
+use partial_init_core::{MissingFields, PartialInit};
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Spell {
+    pub name: String,
+    pub damage: u32,
+    #[default = 5]
+    pub range: u32,
+}
+
+// more required fields than `MissingFields` can track, so calling `try_build` is an error
+#[derive(PartialInit)]
+pub struct Wide(
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8, pub u8,
+    pub u8,
+);
+
+pub fn run() {
+    let spell = Spell::uninit().name(Some("fire".to_owned())).damage(20).try_build();
+    assert_eq!(spell, Ok(Spell { name: "fire".to_owned(), damage: 20, range: 5 }));
+
+    let missing = Spell::uninit().name(None::<String>).try_build().unwrap_err();
+    assert_eq!(missing.len(), 2);
+    assert!(missing.contains("name") && missing.contains("damage"));
+    assert!(!missing.contains("range"));
+    assert_eq!(missing.iter().collect::<Vec<_>>(), ["name", "damage"]);
+    assert_eq!(missing.to_string(), "missing fields: `name`, `damage`");
+
+    let mut missing = MissingFields::new(&["a"]);
+    assert!(missing.is_empty());
+    missing.insert(MissingFields::MAX_FIELDS - 1);
+
+    // the panic is expected, so it isn't printed
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(move || missing.insert(MissingFields::MAX_FIELDS));
+    std::panic::set_hook(hook);
+    assert!(result.is_err());
+}
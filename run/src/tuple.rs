@@ -0,0 +1,24 @@
+// tuple structs name their FIFs after the position of each field
+
+use partial_init_core::{FieldName, PartialInit};
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[deinit]
+pub struct Rgb(#[func_name(red)] pub u8, pub u8, #[default] pub u8);
+
+#[derive(PartialInit, Debug, PartialEq)]
+pub struct Wrapper<T>(pub T);
+
+pub fn run() {
+    let color = Rgb::uninit().red(255)._1(128).build();
+    assert_eq!(color, Rgb(255, 128, 0));
+
+    let color = Rgb::uninit().red(1)._1(2)._2(3).deinit_red().red(4).build();
+    assert_eq!(color, Rgb(4, 2, 3));
+
+    assert_eq!(<__Rgb__::red as FieldName>::NAME, "_0");
+    assert_eq!(<__Rgb__::_1 as FieldName>::NAME, "_1");
+
+    let wrapper = Wrapper::uninit()._0("inner").build();
+    assert_eq!(wrapper, Wrapper("inner"));
+}
@@ -0,0 +1,71 @@
+// `#[validate]` checks fields, and the whole value, when it is built
+
+use block_on;
+use partial_init_core::{FieldName, PartialInit, TryBuildError, ValidationError};
+
+mod checks {
+    use partial_init_core::ValidationError;
+
+    pub fn not_empty<S: AsRef<str>>(name: &S) -> Result<(), &'static str> {
+        if name.as_ref().is_empty() {
+            Err("must not be empty")
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn spell(spell: &super::Spell) -> Result<(), ValidationError> {
+        if spell.damage > 100 && spell.range > 10 {
+            Err(ValidationError::for_field::<super::__Spell__::range>("too strong"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// a validator with the same name as a field
+fn damage(damage: &u32) -> Result<(), &'static str> {
+    if *damage == 0 {
+        Err("must not be zero")
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(validate = checks::spell)]
+pub struct Spell {
+    #[validate(checks::not_empty)]
+    pub name: String,
+    #[validate(damage)]
+    pub damage: u32,
+    #[default = 5]
+    pub range: u32,
+}
+
+pub fn run() {
+    let spell = Spell::uninit().name("fire".to_owned()).damage(10).build().unwrap();
+    assert_eq!(spell.range, 5);
+
+    let error = Spell::uninit().name(String::new()).damage(0).build().unwrap_err();
+    assert_eq!(error.field, Some(<__Spell__::name as FieldName>::INFO));
+    assert_eq!(error.to_string(), "invalid value for field `name`: must not be empty");
+
+    let error = Spell::uninit().name("fire".to_owned()).damage(0).build().unwrap_err();
+    assert_eq!(error.to_string(), "invalid value for field `damage`: must not be zero");
+
+    let error = Spell::uninit().name("fire".to_owned()).damage(200).range(20).build().unwrap_err();
+    assert_eq!(error, ValidationError::for_field::<__Spell__::range>("too strong"));
+
+    let spell = Spell::uninit().name("fire".to_owned()).damage(200).range(20).build_async();
+    assert_eq!(block_on(spell).unwrap_err().message, "too strong");
+
+    match Spell::uninit().name(Some("fire".to_owned())).damage(None).try_build() {
+        Err(TryBuildError::MissingFields(missing)) => assert!(missing.contains("damage")),
+        other => panic!("expected `damage` to be missing, got {:?}", other),
+    }
+    match Spell::uninit().name(Some("fire".to_owned())).damage(Some(0)).try_build() {
+        Err(TryBuildError::Invalid(error)) => assert_eq!(error.message, "must not be zero"),
+        other => panic!("expected `damage` to be invalid, got {:?}", other),
+    }
+}
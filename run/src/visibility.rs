@@ -0,0 +1,76 @@
+// the FIFs and FDFs of a field have the visibility of that field, but never more than the type
+
+use partial_init_core::{BuildError, PartialInit};
+use serde_json;
+
+mod config {
+    use partial_init_core::PartialInit;
+
+    #[derive(PartialInit, Debug, PartialEq)]
+    #[deinit]
+    #[partial_init(dynamic)]
+    pub struct Config {
+        pub host: String,
+        #[default = 3]
+        retries: u32,
+        #[default]
+        #[partial_init(vis = "pub")]
+        pub(crate) port: u16,
+    }
+
+    impl Config {
+        pub fn retries(&self) -> u32 {
+            self.retries
+        }
+    }
+
+    // `retries` can only be set in this module
+    pub fn with_retries(retries: u32) -> Config {
+        Config::uninit().host("inner".to_owned()).retries(retries).deinit_retries().retries(retries + 1).build()
+    }
+
+    // `vis = "pub"` is lowered to the visibility of the type
+    #[derive(PartialInit, Debug, PartialEq)]
+    #[partial_init(vis = "pub")]
+    pub(crate) struct Internal {
+        pub(crate) value: u8,
+    }
+}
+
+// the old behaviour, every function has the visibility of the type
+#[derive(PartialInit, Debug, PartialEq)]
+#[partial_init(vis = "pub")]
+pub struct Everything {
+    value: u8,
+}
+
+pub fn run() {
+    let config = config::Config::uninit().host("outer".to_owned()).port(80).build();
+    assert_eq!((config.host.as_str(), config.port, config.retries()), ("outer", 80, 3));
+
+    assert_eq!(config::with_retries(1).retries(), 2);
+
+    let internal = config::Internal::uninit().value(1).build();
+    assert_eq!(internal.value, 1);
+
+    assert_eq!(Everything::uninit().value(2).build(), Everything { value: 2 });
+
+    // hidden fields are unknown to the dynamic builder
+    let mut builder = config::DynConfigBuilder::new();
+    builder.host("dyn".to_owned());
+    match builder.set_field("retries", Box::new(5u32)) {
+        Err(BuildError::UnknownField(name)) => assert_eq!(name, "retries"),
+        other => panic!("expected `retries` to be unknown, got {:?}", other),
+    }
+    builder.set_field("port", Box::new(8u16)).unwrap();
+    let config = builder.build().unwrap();
+    assert_eq!((config.host.as_str(), config.port, config.retries()), ("dyn", 8, 3));
+
+    // and to serde
+    match serde_json::from_str::<config::DynConfigBuilder>(r#"{ "host": "serde", "retries": 5 }"#) {
+        Err(error) => assert!(error.to_string().contains("unknown field `retries`")),
+        Ok(_) => panic!("expected `retries` to be unknown"),
+    }
+    let builder: config::DynConfigBuilder = serde_json::from_str(r#"{ "host": "serde", "port": 9 }"#).unwrap();
+    assert_eq!(builder.build().unwrap().retries(), 3);
+}